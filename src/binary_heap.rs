@@ -0,0 +1,191 @@
+use std::fmt;
+
+use crate::list::List;
+
+/// A priority queue backed by a `List`, laid out as an implicit binary
+/// max-heap: the children of index `i` live at `2i + 1` and `2i + 2` and
+/// every parent is at least as big as its children. Wrap elements in
+/// `std::cmp::Reverse` to get a min-heap
+pub struct BinaryHeap<T> {
+    list: List<T>,
+}
+
+impl <T: Ord> BinaryHeap<T> {
+    pub fn new() -> BinaryHeap<T> {
+        BinaryHeap { list: List::new() }
+    }
+
+    /// Turns `list` into a heap in place in O(n) by sifting down every
+    /// parent, starting from the last one (Floyd's method)
+    pub fn from_list(list: List<T>) -> BinaryHeap<T> {
+        let mut heap = BinaryHeap { list };
+        let len = heap.list.len();
+        for i in (0..len / 2).rev() {
+            heap.sift_down(i, len);
+        }
+        heap
+    }
+
+    pub fn push(&mut self, val: T) {
+        self.list.push(val);
+        self.sift_up(self.list.len() - 1);
+    }
+
+    /// Removes and returns the greatest element
+    pub fn pop(&mut self) -> Option<T> {
+        let last = self.list.len().checked_sub(1)?;
+        self.list.swap(0, last);
+        let max = self.list.pop();
+        self.sift_down(0, last);
+        max
+    }
+
+    /// Returns the greatest element without removing it
+    pub fn peek(&self) -> Option<&T> {
+        self.list.first()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Consumes the heap and returns its elements in ascending order,
+    /// sorting in place without allocating
+    pub fn into_sorted_list(mut self) -> List<T> {
+        let mut end = self.list.len();
+        while end > 1 {
+            end -= 1;
+            self.list.swap(0, end);
+            self.sift_down(0, end);
+        }
+        self.list
+    }
+
+    /// Moves the element at `i` up until its parent is at least as big
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.list[i] <= self.list[parent] {
+                break;
+            }
+            self.list.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    /// Moves the element at `i` down until both children are no bigger,
+    /// only looking at the first `end` elements
+    fn sift_down(&mut self, mut i: usize, end: usize) {
+        loop {
+            let left = 2 * i + 1;
+            if left >= end {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < end && self.list[right] > self.list[left] { right } else { left };
+            if self.list[i] >= self.list[child] {
+                break;
+            }
+            self.list.swap(i, child);
+            i = child;
+        }
+    }
+}
+
+impl <T: Ord> Default for BinaryHeap<T> {
+    fn default() -> Self {
+        BinaryHeap::new()
+    }
+}
+
+impl <T: Ord> FromIterator<T> for BinaryHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        BinaryHeap::from_list(List::from_iter(iter))
+    }
+}
+
+impl <T: Ord> Extend<T> for BinaryHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+/// Lists the elements in heap order, which is not sorted order
+impl <T: fmt::Debug> fmt::Debug for BinaryHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.list, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::list::list;
+    use std::cmp::Reverse;
+
+    // Helper function to create a new heap
+    fn nh<T: Ord>() -> BinaryHeap<T> {
+        BinaryHeap::new()
+    }
+
+    // Checks that every parent is at least as big as its children
+    fn is_heap<T: Ord>(heap: &BinaryHeap<T>) -> bool {
+        (1..heap.list.len()).all(|i| heap.list[(i - 1) / 2] >= heap.list[i])
+    }
+
+    #[test]
+    fn test_new_heap_is_empty() {
+        let mut heap: BinaryHeap<i32> = nh();
+        assert!(heap.peek().is_none(), "New heap should be empty");
+        assert_eq!(heap.pop(), None, "Pop on empty heap should return None");
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_pop_is_descending() {
+        let mut heap = nh();
+        for val in [5, 1, 8, 3, 9, 2, 8, 7] {
+            heap.push(val);
+            assert!(is_heap(&heap), "Heap property should hold after every push");
+        }
+        assert_eq!(heap.peek(), Some(&9), "Peek should return the greatest element");
+
+        let mut popped = Vec::new();
+        while let Some(val) = heap.pop() {
+            assert!(is_heap(&heap), "Heap property should hold after every pop");
+            popped.push(val);
+        }
+        assert_eq!(popped, vec![9, 8, 8, 7, 5, 3, 2, 1], "Pops should come out in descending order");
+    }
+
+    #[test]
+    fn test_from_list_heapifies() {
+        let heap = BinaryHeap::from_list(list![3, 1, 4, 1, 5, 9, 2, 6, 5, 3, 5]);
+        assert!(is_heap(&heap), "from_list should produce a valid heap");
+        assert_eq!(heap.len(), 11);
+        assert_eq!(heap.peek(), Some(&9));
+
+        let empty: BinaryHeap<i32> = BinaryHeap::from_list(List::new());
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_into_sorted_list() {
+        let heap: BinaryHeap<i32> = [7, 2, 9, 4, 4, 0].into_iter().collect();
+        assert_eq!(heap.into_sorted_list(), [0, 2, 4, 4, 7, 9], "Sorted list should be ascending");
+    }
+
+    #[test]
+    fn test_reverse_is_min_heap() {
+        let mut heap = nh();
+        heap.extend([3, 1, 2].map(Reverse));
+        assert_eq!(heap.pop(), Some(Reverse(1)), "Reverse should turn it into a min-heap");
+        assert_eq!(heap.pop(), Some(Reverse(2)));
+    }
+}
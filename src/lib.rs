@@ -1,5 +1,13 @@
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+#[allow(dead_code)]
+mod binary_heap;
 #[allow(dead_code)]
 mod list;
+#[allow(dead_code)]
+mod sorted_list;
+#[allow(dead_code)]
+mod stack;
 
 pub fn run() {
 }
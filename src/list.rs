@@ -1,34 +1,209 @@
+mod allocator;
 mod raw_list;
 mod iter;
 mod drain;
+mod splice;
 pub mod queue;
 
+pub use allocator::{ Allocator, Global };
 use drain::{ Drain };
 use raw_list::{ RawList };
-use iter::{ IntoIter };
+use iter::{ IntoIter, Iter, IterMut };
+use splice::{ Splice };
 use std::{
-    marker::PhantomData, mem::{self}, ops::{Deref, DerefMut}
+    alloc::Layout,
+    borrow::{Borrow, BorrowMut},
+    collections::{HashMap, HashSet, VecDeque, hash_map::RandomState},
+    fmt,
+    hash::{BuildHasher, Hash},
+    io,
+    marker::PhantomData,
+    mem::{self, MaybeUninit},
+    ops::{Bound, Deref, DerefMut, Range, RangeBounds},
+    ptr::NonNull
 };
 
 use crate::list::iter::RawValIter;
 
-pub struct List<T> {
-    buf: RawList<T>,
+/// Why a fallible reservation failed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TryReserveError {
+    /// The requested capacity overflowed `usize` or exceeded `isize::MAX` bytes
+    CapacityOverflow,
+    /// The allocator couldn't provide memory for the given layout
+    AllocError { layout: Layout },
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryReserveError::CapacityOverflow => f.write_str("capacity overflow"),
+            TryReserveError::AllocError { layout } => {
+                write!(f, "memory allocation of {} bytes failed", layout.size())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TryReserveError {}
+
+/// How much a buffer grows by when it runs out of room
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GrowthStrategy {
+    /// Double the capacity, the default
+    #[default]
+    Double,
+    /// Grow by half the capacity, rounded up. Wastes less memory on huge
+    /// buffers while keeping pushes amortized O(1)
+    OnePointFive,
+    /// Grow by a fixed number of elements. Pushes are no longer amortized
+    /// O(1), but the overshoot is bounded
+    Fixed(usize),
+}
+
+impl GrowthStrategy {
+    /// The capacity to grow to from `cap`, ignoring how much room is needed
+    fn next_cap(self, cap: usize) -> usize {
+        match self {
+            GrowthStrategy::Double => cap.saturating_mul(2),
+            GrowthStrategy::OnePointFive => cap.saturating_add(cap.div_ceil(2).max(1)),
+            GrowthStrategy::Fixed(step) => cap.saturating_add(step),
+        }
+    }
+}
+
+/// Turns `range` into concrete start and end indices into a list of `len`
+/// elements, panicking if it doesn't fit
+fn resolve_range<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&i) => i,
+        Bound::Excluded(&i) => i.checked_add(1).expect("range start overflowed"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&i) => i.checked_add(1).expect("range end overflowed"),
+        Bound::Excluded(&i) => i,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "range start is greater than range end");
+    assert!(end <= len, "range end out of bounds");
+    start..end
+}
+
+/// Builds a `List` the same way `vec!` builds a `Vec`, either from a
+/// list of elements or as `list![elem; n]` with `n` clones of `elem`.
+/// Crate-local like the `list` module itself, import it with
+/// `use crate::list::list`
+#[allow(unused_macros)]
+macro_rules! list {
+    () => {
+        $crate::list::List::new()
+    };
+    ($elem:expr; $n:expr) => {{
+        let n = $n;
+        let elem = $elem;
+        let mut list = $crate::list::List::with_capacity(n);
+        for _ in 0..n {
+            list.push(::std::clone::Clone::clone(&elem));
+        }
+        list
+    }};
+    ($($x:expr),+ $(,)?) => {
+        <$crate::list::List<_> as ::std::iter::FromIterator<_>>::from_iter([$($x),+])
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use list;
+
+pub struct List<T, A: Allocator = Global> {
+    buf: RawList<T, A>,
     len: usize
 }
 
-unsafe impl<T: Send> Send for List<T> {}
-unsafe impl<T: Sync> Sync for List<T> {}
+unsafe impl<T: Send, A: Allocator + Send> Send for List<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for List<T, A> {}
 
 impl <T> List<T> {
+    /// Creates an empty list without allocating. Zero sized types never
+    /// allocate at all, their `len` is just a counter over a dangling pointer
     pub fn new() -> List<T> {
-        assert!(mem::size_of::<T>() != 0, "ZSTs can't be handled yet");
         List { 
             buf: RawList::new(),
             len: 0
         }
     }
 
+    /// Creates an empty list with room for `cap` elements so the first
+    /// `cap` pushes don't need to grow the buffer
+    pub fn with_capacity(cap: usize) -> List<T> {
+        List {
+            buf: RawList::with_capacity(cap),
+            len: 0
+        }
+    }
+
+    /// Creates an empty list that grows its buffer according to `strategy`
+    /// instead of doubling
+    pub fn with_growth(strategy: GrowthStrategy) -> List<T> {
+        assert!(strategy != GrowthStrategy::Fixed(0), "fixed growth step must be non-zero");
+        let mut list = List::new();
+        list.buf.growth = strategy;
+        list
+    }
+
+    /// Collects the items into a new list, stopping at the first `Err`.
+    /// Anything collected before the error is dropped
+    pub fn try_from_iter<E, I: IntoIterator<Item = Result<T, E>>>(iter: I) -> Result<List<T>, E> {
+        let mut list = List::new();
+        for item in iter {
+            list.push(item?);
+        }
+        Ok(list)
+    }
+
+    /// Converts the list into a boxed slice of exactly `len()` elements,
+    /// reallocating first if there is spare capacity
+    pub fn into_boxed_slice(mut self) -> Box<[T]> {
+        self.shrink_to_fit();
+        let list = mem::ManuallyDrop::new(self);
+        unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(list.ptr(), list.len)) }
+    }
+}
+
+impl <T, A: Allocator> List<T, A> {
+    /// Creates an empty list that will allocate from `alloc`
+    pub fn new_in(alloc: A) -> List<T, A> {
+        List {
+            buf: RawList::new_in(alloc),
+            len: 0
+        }
+    }
+
+    /// Creates an empty list with room for `cap` elements, allocated from `alloc`
+    pub fn with_capacity_in(cap: usize, alloc: A) -> List<T, A> {
+        List {
+            buf: RawList::with_capacity_in(cap, alloc),
+            len: 0
+        }
+    }
+
+    /// The allocator backing this list
+    pub fn allocator(&self) -> &A {
+        &self.buf.alloc
+    }
+
+    /// Builds a list in `alloc` by moving `len` elements out of `src`. The
+    /// caller must not use or drop the source elements afterwards
+    unsafe fn moved_from(src: *const T, len: usize, alloc: A) -> List<T, A> {
+        let mut list = List::with_capacity_in(len, alloc);
+        unsafe {
+            std::ptr::copy_nonoverlapping(src, list.ptr(), len);
+        }
+        list.len = len;
+        list
+    }
+
     fn ptr(&self) -> *mut T {
         self.buf.ptr.as_ptr()
     }
@@ -37,6 +212,100 @@ impl <T> List<T> {
         self.buf.cap
     }
 
+    /// Number of elements the list can hold without reallocating.
+    /// Zero sized types report `usize::MAX`
+    pub fn capacity(&self) -> usize {
+        self.cap()
+    }
+
+    /// Borrows the whole list as a slice. Same as going through `Deref`
+    pub fn as_slice(&self) -> &[T] {
+        self
+    }
+
+    /// Borrows the whole list as a mutable slice. Same as going through `DerefMut`
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self
+    }
+
+    /// Consumes the list without freeing its buffer and hands back the
+    /// elements as a slice that lives for as long as the caller wants.
+    /// The buffer and any spare capacity are never reclaimed
+    pub fn leak<'a>(self) -> &'a mut [T] where A: 'a {
+        let list = mem::ManuallyDrop::new(self);
+        let len = list.len;
+        unsafe { std::slice::from_raw_parts_mut(list.ptr(), len) }
+    }
+
+    /// Iterates over shared references to the elements, front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            list: PhantomData,
+            iter: unsafe { RawValIter::new(self) }
+        }
+    }
+
+    /// Iterates over mutable references to the elements, front to back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            list: PhantomData,
+            iter: unsafe { RawValIter::from_raw_parts(self.ptr(), self.len) }
+        }
+    }
+
+    /// Number of bytes reserved by the backing buffer, used or not.
+    /// Always 0 for zero sized types
+    pub fn allocated_bytes(&self) -> usize {
+        self.cap() * mem::size_of::<T>()
+    }
+
+    /// Makes sure there is room for at least `additional` more elements,
+    /// growing the buffer at most once. Does nothing if there is enough room
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len.checked_add(additional).expect("capacity overflow");
+        self.buf.grow_to(required);
+    }
+
+    /// Makes sure there is room for exactly `additional` more elements,
+    /// without rounding the capacity up. Does nothing if there is enough room
+    pub fn reserve_exact(&mut self, additional: usize) {
+        let required = self.len.checked_add(additional).expect("capacity overflow");
+        self.buf.grow_exact(required);
+    }
+
+    /// Like `reserve`, but returns an error instead of panicking or
+    /// aborting when the capacity overflows or the allocator fails
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let required = self.len.checked_add(additional).ok_or(TryReserveError::CapacityOverflow)?;
+        self.buf.try_grow_to(required)
+    }
+
+    /// Sets the length of the list without touching its elements. Meant to
+    /// be used together with `spare_capacity_mut` to commit elements that
+    /// were written into the buffer directly
+    ///
+    /// # Safety
+    ///
+    /// `new_len` must not be greater than `capacity()`, and every element
+    /// in `old_len..new_len` must be initialized. Shrinking the length this
+    /// way does not drop the elements that fall off the end, so they leak
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.cap(), "set_len past capacity");
+        self.len = new_len;
+    }
+
+    /// The uninitialized tail of the buffer, from `len()` up to
+    /// `capacity()`. Writing here doesn't change the length, call `set_len`
+    /// afterwards to make the written elements part of the list
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                self.ptr().add(self.len) as *mut MaybeUninit<T>,
+                self.cap() - self.len,
+            )
+        }
+    }
+
     pub fn push(&mut self, val: T) {
         if self.len == self.cap() {
             self.buf.grow()
@@ -59,6 +328,16 @@ impl <T> List<T> {
         }
     }
 
+    pub fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i >= self.len {
+            None
+        } else {
+            unsafe {
+                Some(&mut *self.ptr().add(i))
+            }
+        }
+    }
+
     pub fn pop(&mut self) -> Option<T> {
         if self.len == 0 {
             None
@@ -70,10 +349,31 @@ impl <T> List<T> {
         }
     }
 
+    /// Pops the last element only if `pred` returns true for it
+    pub fn pop_if<F: FnOnce(&mut T) -> bool>(&mut self, pred: F) -> Option<T> {
+        if pred(self.last_mut()?) {
+            self.pop()
+        } else {
+            None
+        }
+    }
+
+    pub fn first_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut T> {
+        let last = self.len.checked_sub(1)?;
+        self.get_mut(last)
+    }
+
     pub fn insert(&mut self, index: usize, val: T) {
         assert!(index <= self.len, "index out of bounds");
         if self.len == self.cap() { self.buf.grow() }
 
+        // Nothing below can panic, so the list is never seen with the gap
+        // open. `index <= len` keeps the count from underflowing, and
+        // inserting at `len` is a zero-length copy
         unsafe {
             let insert_ptr = self.ptr().add(index);
             std::ptr::copy(
@@ -86,8 +386,59 @@ impl <T> List<T> {
         self.len += 1;
     }
 
+    /// Inserts clones of `values` at `index`, shifting the tail up once to
+    /// make room for all of them. Panics if `index` is greater than the length
+    pub fn insert_slice(&mut self, index: usize, values: &[T]) where T: Clone {
+        assert!(index <= self.len, "index out of bounds");
+        let count = values.len();
+        if count == 0 {
+            return;
+        }
+        self.reserve(count);
+
+        let tail_len = self.len - index;
+        // Only the head is live while the gap is being filled. If a clone
+        // panics the guard moves the tail back down against the clones made
+        // so far, so there are never uninitialized slots in the live range.
+        self.len = index;
+        unsafe {
+            std::ptr::copy(self.ptr().add(index), self.ptr().add(index + count), tail_len);
+        }
+        let mut g = InsertSliceGuard { list: self, index, filled: 0, count, tail_len };
+        for val in values {
+            unsafe {
+                std::ptr::write(g.list.ptr().add(g.index + g.filled), val.clone());
+            }
+            g.filled += 1;
+        }
+    }
+
+    /// Inserts every element produced by the iterator at the front of the
+    /// list, keeping the iterator's order. The iterator is collected first
+    /// so the existing elements only have to be shifted once
+    pub fn prepend_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut batch = List::new();
+        for val in iter {
+            batch.push(val);
+        }
+        let count = batch.len;
+        if count == 0 {
+            return;
+        }
+
+        self.reserve(count);
+        unsafe {
+            std::ptr::copy(self.ptr(), self.ptr().add(count), self.len);
+            std::ptr::copy_nonoverlapping(batch.ptr(), self.ptr(), count);
+            batch.len = 0;
+        }
+        self.len += count;
+    }
+
     pub fn remove(&mut self, index: usize) -> T {
         assert!(index < self.len, "index out of bounds");
+        // After the decrement `index <= len`, so removing the last element
+        // is a zero-length copy
         unsafe {
             self.len -= 1;
             let removed = std::ptr::read(self.ptr().add(index));
@@ -100,20 +451,110 @@ impl <T> List<T> {
         }
     }
 
+    /// Returns a mutable reference to the element at `index`. If the list
+    /// is too short it is first extended by calling `f` for every missing
+    /// slot up to and including `index`
+    pub fn get_or_insert_with<F: FnMut() -> T>(&mut self, index: usize, mut f: F) -> &mut T {
+        while self.len <= index {
+            self.push(f());
+        }
+        unsafe {
+            &mut *self.ptr().add(index)
+        }
+    }
+
+    /// Removes the element at `index` in O(1) by moving the last element
+    /// into its place. Doesn't preserve the order of the list
+    pub fn swap_remove(&mut self, index: usize) -> T {
+        assert!(index < self.len, "index out of bounds");
+        self.len -= 1;
+        unsafe {
+            let removed = std::ptr::read(self.ptr().add(index));
+            if index != self.len {
+                std::ptr::copy_nonoverlapping(self.ptr().add(self.len), self.ptr().add(index), 1);
+            }
+            removed
+        }
+    }
+
+    /// Like `swap_remove`, but only removes the element if `pred` holds for
+    /// it. Returns `None` and leaves the list alone when `pred` fails or
+    /// `index` is out of bounds
+    pub fn swap_remove_if<F: FnOnce(&T) -> bool>(&mut self, index: usize, pred: F) -> Option<T> {
+        if pred(self.get(index)?) {
+            Some(self.swap_remove(index))
+        } else {
+            None
+        }
+    }
+
     pub fn get_unchecked(&self, i: usize) -> &T {
         unsafe {
             &*self.ptr().add(i)
         }
     }
 
-    pub fn drain<'a>(&mut self) -> Drain<'a, T> {
-        let iter = unsafe { RawValIter::new(&self) };
+    /// Mutable counterpart of `get_unchecked`
+    ///
+    /// # Safety
+    ///
+    /// `i` must be less than `len()`
+    pub unsafe fn get_unchecked_mut(&mut self, i: usize) -> &mut T {
+        debug_assert!(i < self.len, "get_unchecked_mut out of bounds");
+        unsafe {
+            &mut *self.ptr().add(i)
+        }
+    }
+
+    /// Removes the elements in `range` and yields them by value. When the
+    /// `Drain` is dropped any elements it didn't yield are dropped and the
+    /// tail is shifted down to close the gap. Panics if the range is out of
+    /// bounds or its start is past its end
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> Drain<'_, T, A> {
+        let Range { start, end } = resolve_range(range, self.len);
+        let tail_len = self.len - end;
 
-        self.len = 0;
+        // Cut the list off at `start` up front, if the `Drain` is forgotten
+        // the drained range and the tail leak rather than being read twice
+        self.len = start;
+        let iter = unsafe { RawValIter::from_raw_parts(self.ptr().add(start), end - start) };
 
         Drain {
+            list: NonNull::from(self),
             iter,
-            list: PhantomData,
+            tail_start: end,
+            tail_len,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Replaces the elements in `range` with the items of `replace_with`.
+    /// The removed elements are yielded by the returned iterator and the
+    /// replacements are put in place when it is dropped. Panics on the same
+    /// ranges as `drain`
+    pub fn splice<R, I>(&mut self, range: R, replace_with: I) -> Splice<'_, T, I::IntoIter, A>
+    where
+        R: RangeBounds<usize>,
+        I: IntoIterator<Item = T>,
+    {
+        Splice {
+            drain: self.drain(range),
+            replace_with: replace_with.into_iter(),
+        }
+    }
+
+    /// Consumes the list and yields its elements from last to first.
+    /// Elements that are never yielded are dropped with the iterator
+    pub fn into_iter_rev(self) -> impl Iterator<Item = T> {
+        self.into_iter().rev()
+    }
+
+    /// Calls `f` on every element in place, front to back
+    pub fn apply<F: FnMut(&mut T)>(&mut self, mut f: F) {
+        for i in 0..self.len {
+            unsafe {
+                f(&mut *self.ptr().add(i));
+            }
         }
     }
 
@@ -122,7 +563,7 @@ impl <T> List<T> {
     /// than or equal to the current length of the list this has no effect
     pub fn truncate(&mut self, len: usize) {
         unsafe {
-            if len > self.len {
+            if len >= self.len {
                 return;
             }
             let remaining_len = self.len - len;
@@ -131,779 +572,3430 @@ impl <T> List<T> {
             std::ptr::drop_in_place(s);
         }
     }
-}
 
-impl <T> Drop for List<T> {
-    fn drop(&mut self) {
-        while let Some(_) = self.pop() { }
+    /// Resizes the list to `new_len`. Growing fills the new slots with clones
+    /// of `value`, moving `value` itself into the last one. Shrinking drops
+    /// the tail the same way `truncate` does
+    pub fn resize(&mut self, new_len: usize, value: T) where T: Clone {
+        if new_len <= self.len {
+            self.truncate(new_len);
+            return;
+        }
+
+        self.reserve(new_len - self.len);
+        while self.len < new_len - 1 {
+            self.push(value.clone());
+        }
+        self.push(value);
     }
-}
 
-impl <T> Deref for List<T> {
-    type Target = [T];
+    /// Resizes the list to `new_len`, calling `f` to produce every new
+    /// element when growing. Shrinking drops the tail like `truncate`
+    pub fn resize_with<F: FnMut() -> T>(&mut self, new_len: usize, mut f: F) {
+        if new_len <= self.len {
+            self.truncate(new_len);
+            return;
+        }
 
-    fn deref(&self) -> &Self::Target {
-        unsafe {
-            std::slice::from_raw_parts(self.ptr(), self.len)
+        self.reserve(new_len - self.len);
+        while self.len < new_len {
+            self.push(f());
         }
     }
-}
 
-impl <T> DerefMut for List<T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        unsafe {
-            std::slice::from_raw_parts_mut(self.ptr(), self.len)
+    /// Moves the first element out of the list and returns it together with
+    /// the remaining elements, or `None` if the list is empty
+    pub fn split_first_owned(mut self) -> Option<(T, List<T, A>)> {
+        if self.len == 0 {
+            None
+        } else {
+            let first = self.remove(0);
+            Some((first, self))
         }
     }
-}
-
-impl <T> IntoIterator for List<T> {
-    type Item = T;
 
-    type IntoIter = IntoIter<T>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        unsafe {
-            let iter = RawValIter::new(&self);
+    /// Moves the last element out of the list and returns it together with
+    /// the remaining elements, or `None` if the list is empty
+    pub fn split_last_owned(mut self) -> Option<(T, List<T, A>)> {
+        let last = self.pop()?;
+        Some((last, self))
+    }
 
-            let buf = std::ptr::read(&self.buf);
-            mem::forget(self);
+    /// Removes consecutive elements for which `same(current, previous)`
+    /// returns true and reports how many were removed. This is the
+    /// compaction engine every dedup variant goes through.
+    pub fn dedup_by_reporting<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same: F) -> usize {
+        let len = self.len;
+        if len <= 1 {
+            return 0;
+        }
 
-            IntoIter {
-                iter,
-                _buf: buf
+        // The first element is always kept. The guard restores `len` when
+        // we're done, or closes the gap if `same` or a `Drop` panics.
+        self.len = 0;
+        let mut g = RetainGuard { list: self, read: 1, write: 1, len };
+        while g.read < g.len {
+            unsafe {
+                let cur = g.list.ptr().add(g.read);
+                let prev = g.list.ptr().add(g.write - 1);
+                if same(&mut *cur, &mut *prev) {
+                    g.read += 1;
+                    std::ptr::drop_in_place(cur);
+                } else {
+                    // Survivors that haven't moved yet don't need to be copied onto themselves
+                    if g.read != g.write {
+                        std::ptr::copy_nonoverlapping(cur, g.list.ptr().add(g.write), 1);
+                    }
+                    g.write += 1;
+                    g.read += 1;
+                }
             }
         }
+        g.read - g.write
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Removes consecutive elements for which `same(current, previous)` returns true.
+    /// Same argument order as `Vec::dedup_by`: `previous` is the element that
+    /// stays, so the first element of every run is the one kept
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, same: F) {
+        self.dedup_by_reporting(same);
+    }
 
-    // Helper function to create a new list
-    fn nl<T>() -> List<T> {
-        List::new()
+    /// Removes consecutive elements that map to the same key
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b))
     }
 
-    #[test]
-    fn test_new_list_is_empty() {
-        let list: List<i32> = nl();
-        assert_eq!(list.get(0), None, "New list should be empty");
+    /// Drops every element but keeps the allocated capacity for reuse
+    pub fn clear(&mut self) {
+        let elems: *mut [T] = self.as_mut_slice();
+        // Set len first so a panicking destructor can't lead to a double drop
+        self.len = 0;
+        unsafe {
+            std::ptr::drop_in_place(elems);
+        }
     }
 
-    #[test]
-    fn test_push_single_element() {
-        let mut list = nl();
-        list.push(42);
-        assert_eq!(list.get(0), Some(&42), "Get should return pushed element");
+    /// Truncates the list to `len` and then releases any capacity
+    /// beyond the new length. If `len` is greater than or equal to the
+    /// current length nothing is dropped but the list is still shrunk to fit
+    pub fn truncate_and_shrink(&mut self, len: usize) {
+        self.truncate(len);
+        self.shrink_to_fit();
     }
 
-    #[test]
-    fn test_push_multiple_elements() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        assert_eq!(list.get(0), Some(&1), "Get index 0 should return 1");
-        assert_eq!(list.get(1), Some(&2), "Get index 1 should return 2");
-        assert_eq!(list.get(2), Some(&3), "Get index 2 should return 3");
+    /// Reallocates the buffer down to exactly the current length, freeing
+    /// it entirely when the list is empty
+    pub fn shrink_to_fit(&mut self) {
+        self.buf.shrink_to(self.len);
     }
 
-    #[test]
-    fn test_pop_single_element() {
-        let mut list = nl();
-        list.push(42);
-        assert_eq!(list.pop(), Some(42), "Pop should return the pushed element");
-        assert_eq!(list.get(0), None, "List should be empty after pop");
+    /// Shrinks the buffer down to the current length, but only when the list
+    /// is using less than `1 / ratio` of its capacity. A `ratio` of 4.0
+    /// shrinks lists that are less than a quarter full. Panics if `ratio`
+    /// is less than 1.0 or isn't finite
+    pub fn shrink_if_oversized(&mut self, ratio: f64) {
+        assert!(ratio.is_finite() && ratio >= 1.0, "shrink ratio must be finite and at least 1.0, got {}", ratio);
+        if (self.len as f64) < self.cap() as f64 / ratio {
+            self.shrink_to_fit();
+        }
     }
 
-    #[test]
-    fn test_pop_multiple_elements() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        assert_eq!(list.pop(), Some(3), "First pop should return last element");
-        assert_eq!(list.pop(), Some(2), "Second pop should return second-to-last element");
-        assert_eq!(list.pop(), Some(1), "Third pop should return first element");
-        assert_eq!(list.pop(), None, "Pop on empty list should return None");
+    /// Consumes the list and yields owned chunks of `size` elements counting
+    /// from the end. The first chunk is the tail of the list and the last one
+    /// is the head, which may be shorter than `size`
+    pub fn rchunks_owned(mut self, size: usize) -> impl Iterator<Item = List<T, A>> where A: Clone {
+        assert!(size != 0, "chunk size must be non-zero");
+        std::iter::from_fn(move || {
+            if self.len == 0 {
+                None
+            } else {
+                let at = self.len.saturating_sub(size);
+                Some(self.split_off(at))
+            }
+        })
     }
 
-    #[test]
-    fn test_get_out_of_bounds() {
-        let mut list = nl();
-        list.push(1);
-        assert_eq!(list.get(1), None, "Get beyond list length should return None");
+    /// Swaps the elements at `a` and `b`. Panics if either is out of bounds
+    pub fn swap(&mut self, a: usize, b: usize) {
+        assert!(a < self.len, "swap index a out of bounds: a is {} but len is {}", a, self.len);
+        assert!(b < self.len, "swap index b out of bounds: b is {} but len is {}", b, self.len);
+        self.as_mut_slice().swap(a, b);
     }
 
-    #[test]
-    fn test_index_operator() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        assert_eq!(list[0], 1, "Index 0 should return 1");
-        assert_eq!(list[1], 2, "Index 1 should return 2");
+    /// Rotates the list in place so that the element at `mid` comes first.
+    /// Panics if `mid` is greater than the length
+    pub fn rotate_left(&mut self, mid: usize) {
+        assert!(mid <= self.len, "mid out of bounds: mid is {} but len is {}", mid, self.len);
+        self.as_mut_slice().rotate_left(mid);
     }
 
-    #[test]
-    #[should_panic(expected = "index out of bounds")]
-    fn test_index_operator_out_of_bounds() {
-        let list: List<i32> = nl();
-        let _ = list[0]; // Should panic since list is empty
+    /// Rotates the list in place so that the last `k` elements come first.
+    /// Panics if `k` is greater than the length
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(k <= self.len, "k out of bounds: k is {} but len is {}", k, self.len);
+        self.as_mut_slice().rotate_right(k);
     }
 
-    #[test]
-    fn test_get_unchecked() {
-        let mut list = nl();
-        list.push(42);
-        assert_eq!(list.get_unchecked(0), &42, "get_unchecked should return element at index");
+    /// Consumes the list and returns a new one with the elements rotated so
+    /// that the element at `mid` comes first
+    pub fn rotated_left(mut self, mid: usize) -> List<T, A> where A: Clone {
+        assert!(mid <= self.len, "mid out of bounds");
+        let len = self.len;
+        let mut rotated = List::with_capacity_in(len, self.buf.alloc.clone());
+        unsafe {
+            std::ptr::copy_nonoverlapping(self.ptr().add(mid), rotated.ptr(), len - mid);
+            std::ptr::copy_nonoverlapping(self.ptr(), rotated.ptr().add(len - mid), mid);
+        }
+        // Ownership of the elements moved to `rotated`
+        self.len = 0;
+        rotated.len = len;
+        rotated
     }
 
-    #[test]
-    fn test_list_with_strings() {
-        let mut list: List<String> = nl();
-        list.push(String::from("hello"));
-        list.push(String::from("world"));
-        assert_eq!(list.get(0), Some(&String::from("hello")), "Get index 0 should return 'hello'");
-        assert_eq!(list[1], String::from("world"), "Index 1 should return 'world'");
-        assert_eq!(list.pop(), Some(String::from("world")), "Pop should return last string");
+    /// Consumes the list and returns a new one with the elements rotated so
+    /// that the last `k` elements come first
+    pub fn rotated_right(self, k: usize) -> List<T, A> where A: Clone {
+        assert!(k <= self.len, "k out of bounds");
+        let mid = self.len - k;
+        self.rotated_left(mid)
+    }
+
+    /// Splits the list in two at `at`. `self` keeps the first `at` elements
+    /// and the rest are moved, in order, into a new list sized to fit them.
+    /// Panics if `at` is greater than the length
+    pub fn split_off(&mut self, at: usize) -> List<T, A> where A: Clone {
+        assert!(at <= self.len, "index out of bounds");
+        let tail = unsafe { List::moved_from(self.ptr().add(at), self.len - at, self.buf.alloc.clone()) };
+        self.len = at;
+        tail
+    }
+
+    /// Moves every element of `other` onto the end of `self`, leaving
+    /// `other` empty. `other` keeps its allocation for reuse
+    pub fn append(&mut self, other: &mut List<T, A>) {
+        let count = other.len;
+        self.reserve(count);
+        unsafe {
+            std::ptr::copy_nonoverlapping(other.ptr(), self.ptr().add(self.len), count);
+        }
+        other.len = 0;
+        self.len += count;
+    }
+
+    /// Clones every element of `other` onto the end of the list. If a
+    /// `clone` panics the elements cloned so far stay in the list
+    pub fn extend_from_slice(&mut self, other: &[T]) where T: Clone {
+        self.reserve(other.len());
+        for val in other {
+            self.push(val.clone());
+        }
+    }
+
+    /// Same as `extend_from_slice` for `Copy` types, but copies the whole
+    /// slice in a single memcpy
+    pub fn extend_from_slice_copy(&mut self, other: &[T]) where T: Copy {
+        self.reserve(other.len());
+        unsafe {
+            std::ptr::copy_nonoverlapping(other.as_ptr(), self.ptr().add(self.len), other.len());
+        }
+        self.len += other.len();
+    }
+
+    /// Removes every element whose key has already been seen earlier in the
+    /// list, duplicates don't need to be next to each other. The first
+    /// occurrence of each key is kept and the order is preserved
+    pub fn dedup_total_by_key<K: Hash + Eq, F: FnMut(&T) -> K>(&mut self, mut key: F) {
+        let mut seen = HashSet::new();
+        self.retain_in_place(|val| seen.insert(key(val)));
+    }
+
+    /// Keeps only the elements for which `f` returns true, dropping the rest
+    /// in place and preserving the order of the survivors
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_in_place(|val| f(val));
+    }
+
+    /// Like `retain`, but the predicate gets a mutable reference so it can
+    /// update an element before deciding whether to keep it
+    pub fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, f: F) {
+        self.retain_in_place(f);
+    }
+
+    /// Like `retain`, but stops keeping elements once `n` of them have
+    /// matched. Everything after the `n`th match is dropped
+    pub fn retain_first_n<F: FnMut(&T) -> bool>(&mut self, n: usize, mut f: F) {
+        let mut kept = 0;
+        self.retain_in_place(|val| {
+            if kept < n && f(val) {
+                kept += 1;
+                true
+            } else {
+                false
+            }
+        });
+    }
+
+    /// Drops the elements the predicate rejects, shifting the survivors down
+    /// in a single pass. Returns how many elements were removed
+    fn retain_in_place<F: FnMut(&mut T) -> bool>(&mut self, mut f: F) -> usize {
+        let len = self.len;
+
+        // Nothing is considered initialized while we work. The guard restores
+        // `len` when we're done, or closes the gap if `f` or a `Drop` panics.
+        self.len = 0;
+        let mut g = RetainGuard { list: self, read: 0, write: 0, len };
+        while g.read < g.len {
+            unsafe {
+                let cur = g.list.ptr().add(g.read);
+                if f(&mut *cur) {
+                    if g.read != g.write {
+                        std::ptr::copy_nonoverlapping(cur, g.list.ptr().add(g.write), 1);
+                    }
+                    g.write += 1;
+                    g.read += 1;
+                } else {
+                    // Count the element as processed first so a panicking
+                    // destructor doesn't get it dropped a second time
+                    g.read += 1;
+                    std::ptr::drop_in_place(cur);
+                }
+            }
+        }
+        g.read - g.write
+    }
+
+    /// Keeps the elements matching the predicate in the list and returns
+    /// the rest in a new list. Both keep their original relative order and
+    /// the split is done in a single pass over the list.
+    pub fn retain_split<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> List<T, A> where A: Clone {
+        let len = self.len;
+        let mut removed = List::new_in(self.buf.alloc.clone());
+
+        // Same compaction as `retain_in_place`, except rejected elements are
+        // moved into `removed` instead of dropped. The guard restores `len`
+        // when we're done, or closes the gap if `pred` panics.
+        self.len = 0;
+        let mut g = RetainGuard { list: self, read: 0, write: 0, len };
+        while g.read < g.len {
+            unsafe {
+                let cur = g.list.ptr().add(g.read);
+                if pred(&*cur) {
+                    if g.read != g.write {
+                        std::ptr::copy_nonoverlapping(cur, g.list.ptr().add(g.write), 1);
+                    }
+                    g.write += 1;
+                    g.read += 1;
+                } else {
+                    // Count the element as moved out before `push` can panic
+                    g.read += 1;
+                    removed.push(std::ptr::read(cur));
+                }
+            }
+        }
+        removed
+    }
+}
+
+impl <T: PartialEq, A: Allocator> List<T, A> {
+    /// Removes consecutive repeated elements, keeping the first of each run
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b)
+    }
+}
+
+impl <T: Hash + Eq, A: Allocator> List<T, A> {
+    /// Appends the items from the iterator, skipping any that are already in
+    /// the list or were appended earlier in the same call
+    pub fn extend_dedup<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let hasher = RandomState::new();
+        // Indices of the elements with each hash, so the lookup table never
+        // borrows the list while we push into it
+        let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+        for (i, val) in self.iter().enumerate() {
+            seen.entry(hasher.hash_one(val)).or_default().push(i);
+        }
+
+        for val in iter {
+            let indices = seen.entry(hasher.hash_one(&val)).or_default();
+            if indices.iter().any(|&i| self[i] == val) {
+                continue;
+            }
+            indices.push(self.len);
+            self.push(val);
+        }
+    }
+
+    /// Removes an element if an equal one is among the previous `window`
+    /// kept elements. Repeats further apart than that survive
+    pub fn dedup_window(&mut self, window: usize) {
+        if window == 0 {
+            return;
+        }
+        let hasher = RandomState::new();
+        // Hashes of the most recently kept elements, oldest first. Only
+        // elements whose hashes match need the full equality check
+        let mut recent: VecDeque<u64> = VecDeque::with_capacity(window);
+        let len = self.len;
+
+        // The guard restores `len` when we're done, or closes the gap if
+        // `eq`, `hash` or a `Drop` panics.
+        self.len = 0;
+        let mut g = RetainGuard { list: self, read: 0, write: 0, len };
+        while g.read < g.len {
+            unsafe {
+                let cur = g.list.ptr().add(g.read);
+                let hash = hasher.hash_one(&*cur);
+                let window_start = g.write - recent.len();
+                let is_repeat = recent.iter().enumerate().any(|(j, h)| {
+                    *h == hash && *g.list.ptr().add(window_start + j) == *cur
+                });
+
+                if is_repeat {
+                    // Count the element as processed first so a panicking
+                    // destructor doesn't get it dropped a second time
+                    g.read += 1;
+                    std::ptr::drop_in_place(cur);
+                } else {
+                    if g.read != g.write {
+                        std::ptr::copy_nonoverlapping(cur, g.list.ptr().add(g.write), 1);
+                    }
+                    g.write += 1;
+                    g.read += 1;
+                    if recent.len() == window {
+                        recent.pop_front();
+                    }
+                    recent.push_back(hash);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl <T: bytemuck::Pod, A: Allocator> List<T, A> {
+    /// Views the elements as raw bytes, `len * size_of::<T>()` of them
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(self.as_slice())
+    }
+
+    /// Views the elements as mutable raw bytes. Any bit pattern written
+    /// is a valid `T` thanks to the `Pod` bound
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        bytemuck::cast_slice_mut(self.as_mut_slice())
+    }
+}
+
+/// Keeps a list consistent during an in-place compaction. Elements before
+/// `write` have been kept, elements from `read` to `len` haven't been visited.
+struct RetainGuard<'a, T, A: Allocator> {
+    list: &'a mut List<T, A>,
+    read: usize,
+    write: usize,
+    len: usize,
+}
+
+impl <'a, T, A: Allocator> Drop for RetainGuard<'a, T, A> {
+    fn drop(&mut self) {
+        let unvisited = self.len - self.read;
+        if unvisited > 0 && self.read != self.write {
+            unsafe {
+                std::ptr::copy(
+                    self.list.ptr().add(self.read),
+                    self.list.ptr().add(self.write),
+                    unvisited
+                );
+            }
+        }
+        self.list.len = self.write + unvisited;
+    }
+}
+
+struct InsertSliceGuard<'a, T, A: Allocator> {
+    list: &'a mut List<T, A>,
+    index: usize,
+    filled: usize,
+    count: usize,
+    tail_len: usize,
+}
+
+impl <'a, T, A: Allocator> Drop for InsertSliceGuard<'a, T, A> {
+    fn drop(&mut self) {
+        let gap_end = self.index + self.filled;
+        if self.filled != self.count {
+            unsafe {
+                std::ptr::copy(
+                    self.list.ptr().add(self.index + self.count),
+                    self.list.ptr().add(gap_end),
+                    self.tail_len
+                );
+            }
+        }
+        self.list.len = gap_end + self.tail_len;
+    }
+}
+
+impl <T, A: Allocator> Drop for List<T, A> {
+    fn drop(&mut self) {
+        while self.pop().is_some() { }
+    }
+}
+
+impl <T, A: Allocator> Deref for List<T, A> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        unsafe {
+            std::slice::from_raw_parts(self.ptr(), self.len)
+        }
+    }
+}
+
+impl <T, A: Allocator> DerefMut for List<T, A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe {
+            std::slice::from_raw_parts_mut(self.ptr(), self.len)
+        }
+    }
+}
+
+impl <T, A: Allocator> AsRef<[T]> for List<T, A> {
+    fn as_ref(&self) -> &[T] {
+        self
+    }
+}
+
+impl <T, A: Allocator> AsMut<[T]> for List<T, A> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl <T, A: Allocator> Borrow<[T]> for List<T, A> {
+    fn borrow(&self) -> &[T] {
+        self
+    }
+}
+
+impl <T, A: Allocator> BorrowMut<[T]> for List<T, A> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl <T: fmt::Debug, A: Allocator> fmt::Debug for List<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+/// Prints the elements like `[a, b, c]` using their `Display` impls
+impl <T: fmt::Display, A: Allocator> fmt::Display for List<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[")?;
+        for (i, val) in self.iter().enumerate() {
+            if i != 0 {
+                f.write_str(", ")?;
+            }
+            val.fmt(f)?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl <T: PartialEq<U>, U, A: Allocator, B: Allocator> PartialEq<List<U, B>> for List<T, A> {
+    fn eq(&self, other: &List<U, B>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl <T: Eq, A: Allocator> Eq for List<T, A> {}
+
+impl <T: PartialEq<U>, U, A: Allocator> PartialEq<[U]> for List<T, A> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <T: PartialEq<U>, U, A: Allocator> PartialEq<&[U]> for List<T, A> {
+    fn eq(&self, other: &&[U]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl <T: PartialEq<U>, U, A: Allocator> PartialEq<Vec<U>> for List<T, A> {
+    fn eq(&self, other: &Vec<U>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl <T: PartialEq<U>, U, A: Allocator, const N: usize> PartialEq<[U; N]> for List<T, A> {
+    fn eq(&self, other: &[U; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl <T: PartialOrd, A: Allocator> PartialOrd for List<T, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl <T: Ord, A: Allocator> Ord for List<T, A> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl <T: Hash, A: Allocator> Hash for List<T, A> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl <T> Default for List<T> {
+    fn default() -> Self {
+        List::new()
+    }
+}
+
+impl <T: Clone, A: Allocator + Clone> Clone for List<T, A> {
+    fn clone(&self) -> Self {
+        // `push` bumps len after every write, so if a `clone` panics the
+        // partial list only drops the elements that were actually cloned
+        let mut list = List::with_capacity_in(self.len, self.buf.alloc.clone());
+        list.buf.growth = self.buf.growth;
+        for val in self.iter() {
+            list.push(val.clone());
+        }
+        list
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.clear();
+        self.extend(source.iter().cloned());
+    }
+}
+
+impl <T> From<Vec<T>> for List<T> {
+    /// Takes over the `Vec`'s buffer without copying. Both types allocate
+    /// `Layout::array::<T>(cap)` from the global allocator, so the buffer
+    /// can later be freed by `RawList`
+    fn from(vec: Vec<T>) -> Self {
+        let mut vec = mem::ManuallyDrop::new(vec);
+        let (ptr, len, cap) = (vec.as_mut_ptr(), vec.len(), vec.capacity());
+        let mut buf: RawList<T> = RawList::new();
+        if mem::size_of::<T>() != 0 {
+            buf.cap = cap;
+        }
+        buf.ptr = unsafe { NonNull::new_unchecked(ptr) };
+        List { buf, len }
+    }
+}
+
+impl <T> From<List<T>> for Vec<T> {
+    /// Hands the list's buffer over to a `Vec` without copying
+    fn from(list: List<T>) -> Self {
+        let list = mem::ManuallyDrop::new(list);
+        unsafe { Vec::from_raw_parts(list.ptr(), list.len, list.cap()) }
+    }
+}
+
+impl <T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl <T, A: Allocator> Extend<T> for List<T, A> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for val in iter {
+            self.push(val);
+        }
+    }
+}
+
+impl <'a, T: Copy + 'a, A: Allocator> Extend<&'a T> for List<T, A> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
+/// Lets `write!` use a byte list as a UTF-8 string builder
+impl <A: Allocator> fmt::Write for List<u8, A> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.extend_from_slice_copy(s.as_bytes());
+        Ok(())
+    }
+
+    fn write_char(&mut self, c: char) -> fmt::Result {
+        // A char encodes to at most 4 bytes
+        let mut bytes = [0; 4];
+        self.extend_from_slice_copy(c.encode_utf8(&mut bytes).as_bytes());
+        Ok(())
+    }
+}
+
+impl <A: Allocator> io::Write for List<u8, A> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.extend_from_slice_copy(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl <T: serde::Serialize, A: Allocator> serde::Serialize for List<T, A> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl <'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for List<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ListVisitor<T>(PhantomData<T>);
+
+        impl <'de, T: serde::Deserialize<'de>> serde::de::Visitor<'de> for ListVisitor<T> {
+            type Value = List<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a sequence")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                // Don't trust the hint blindly, a hostile input could claim
+                // billions of elements and make us allocate for all of them
+                let mut list = List::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                while let Some(val) = seq.next_element()? {
+                    list.push(val);
+                }
+                Ok(list)
+            }
+        }
+
+        deserializer.deserialize_seq(ListVisitor(PhantomData))
+    }
+}
+
+impl <T, A: Allocator> IntoIterator for List<T, A> {
+    type Item = T;
+
+    type IntoIter = IntoIter<T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        unsafe {
+            let iter = RawValIter::from_raw_parts(self.ptr(), self.len);
+
+            let buf = std::ptr::read(&self.buf);
+            mem::forget(self);
+
+            IntoIter {
+                iter,
+                _buf: buf
+            }
+        }
+    }
+}
+
+impl <'a, T, A: Allocator> IntoIterator for &'a List<T, A> {
+    type Item = &'a T;
+
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl <'a, T, A: Allocator> IntoIterator for &'a mut List<T, A> {
+    type Item = &'a mut T;
+
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate stats_alloc;
+
+    use stats_alloc::{Region, StatsAlloc, INSTRUMENTED_SYSTEM};
+    use std::{alloc::System, cell::Cell};
+
+    #[global_allocator]
+    pub(super) static GLOBAL: &StatsAlloc<System> = &INSTRUMENTED_SYSTEM;
+
+    // Helper function to create a new list
+    fn nl<T>() -> List<T> {
+        List::new()
+    }
+
+    // Increments the shared counter when dropped
+    pub(super) struct DropCounter<'a>(pub(super) &'a Cell<usize>);
+
+    impl <'a> Drop for DropCounter<'a> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_new_list_is_empty() {
+        let list: List<i32> = nl();
+        assert_eq!(list.get(0), None, "New list should be empty");
+    }
+
+    #[test]
+    fn test_push_single_element() {
+        let mut list = nl();
+        list.push(42);
+        assert_eq!(list.get(0), Some(&42), "Get should return pushed element");
+    }
+
+    #[test]
+    fn test_push_multiple_elements() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.get(0), Some(&1), "Get index 0 should return 1");
+        assert_eq!(list.get(1), Some(&2), "Get index 1 should return 2");
+        assert_eq!(list.get(2), Some(&3), "Get index 2 should return 3");
+    }
+
+    #[test]
+    fn test_pop_single_element() {
+        let mut list = nl();
+        list.push(42);
+        assert_eq!(list.pop(), Some(42), "Pop should return the pushed element");
+        assert_eq!(list.get(0), None, "List should be empty after pop");
+    }
+
+    #[test]
+    fn test_pop_multiple_elements() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.pop(), Some(3), "First pop should return last element");
+        assert_eq!(list.pop(), Some(2), "Second pop should return second-to-last element");
+        assert_eq!(list.pop(), Some(1), "Third pop should return first element");
+        assert_eq!(list.pop(), None, "Pop on empty list should return None");
+    }
+
+    #[test]
+    fn test_get_out_of_bounds() {
+        let mut list = nl();
+        list.push(1);
+        assert_eq!(list.get(1), None, "Get beyond list length should return None");
+    }
+
+    #[test]
+    fn test_index_operator() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        assert_eq!(list[0], 1, "Index 0 should return 1");
+        assert_eq!(list[1], 2, "Index 1 should return 2");
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_operator_out_of_bounds() {
+        let list: List<i32> = nl();
+        let _ = list[0]; // Should panic since list is empty
+    }
+
+    #[test]
+    fn test_get_unchecked() {
+        let mut list = nl();
+        list.push(42);
+        assert_eq!(list.get_unchecked(0), &42, "get_unchecked should return element at index");
+    }
+
+    #[test]
+    fn test_list_with_strings() {
+        let mut list: List<String> = nl();
+        list.push(String::from("hello"));
+        list.push(String::from("world"));
+        assert_eq!(list.get(0), Some(&String::from("hello")), "Get index 0 should return 'hello'");
+        assert_eq!(list[1], String::from("world"), "Index 1 should return 'world'");
+        assert_eq!(list.pop(), Some(String::from("world")), "Pop should return last string");
+    }
+
+    #[test]
+    fn test_multiple_operations() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.get(0), Some(&1), "Get index 0 should return 1");
+        assert_eq!(list.pop(), Some(2), "Pop should return 2");
+        list.push(3);
+        assert_eq!(list[0], 1, "Index 0 should still return 1");
+        assert_eq!(list.get(1), Some(&3), "Get index 1 should return 3");
+        assert_eq!(list.pop(), Some(3), "Pop should return 3");
+        assert_eq!(list.pop(), Some(1), "Pop should return 1");
+        assert_eq!(list.pop(), None, "Pop on empty list should return None");
+    }
+
+    #[test]
+    fn test_push_pop_empty() {
+        let mut list = nl();
+        assert_eq!(list.pop(), None, "Pop on empty list should return None");
+        list.push(1);
+        list.pop();
+        assert_eq!(list.get(0), None, "List should be empty after push and pop");
+    }
+
+    #[test]
+    fn test_insert_into_empty_list() {
+        let mut list: List<i32> = nl();
+        list.insert(0, 42);
+        assert_eq!(list.get(0), Some(&42), "Insert into empty list should place element at index 0");
+        assert_eq!(list.len, 1, "Length should be 1 after insert");
+        assert_eq!(list.get(1), None, "Index 1 should be out of bounds");
+    }
+
+    #[test]
+    fn test_insert_at_start() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.insert(0, 0);
+        assert_eq!(list.get(0), Some(&0), "Insert at start should place element at index 0");
+        assert_eq!(list.get(1), Some(&1), "Original first element should be shifted");
+        assert_eq!(list.get(2), Some(&2), "Original second element should be shifted");
+        assert_eq!(list.len, 3, "Length should be 3 after insert");
+    }
+
+    #[test]
+    fn test_insert_at_end() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.insert(2, 3);
+        assert_eq!(list.get(0), Some(&1), "First element should remain");
+        assert_eq!(list.get(1), Some(&2), "Second element should remain");
+        assert_eq!(list.get(2), Some(&3), "Insert at end should place element at index 2");
+        assert_eq!(list.len, 3, "Length should be 3 after insert");
+    }
+
+    #[test]
+    fn test_insert_in_middle() {
+        let mut list = nl();
+        list.push(1);
+        list.push(3);
+        list.insert(1, 2);
+        assert_eq!(list.get(0), Some(&1), "First element should remain");
+        assert_eq!(list.get(1), Some(&2), "Inserted element should be at index 1");
+        assert_eq!(list.get(2), Some(&3), "Original second element should be shifted");
+        assert_eq!(list.len, 3, "Length should be 3 after insert");
+    }
+
+    #[test]
+    fn test_insert_with_strings() {
+        let mut list: List<String> = nl();
+        list.push(String::from("a"));
+        list.push(String::from("c"));
+        list.insert(1, String::from("b"));
+        assert_eq!(list.get(0), Some(&String::from("a")), "First element should remain");
+        assert_eq!(list.get(1), Some(&String::from("b")), "Inserted element should be at index 1");
+        assert_eq!(list.get(2), Some(&String::from("c")), "Original second element should be shifted");
+        assert_eq!(list.len, 3, "Length should be 3 after insert");
+    }
+
+    #[test]
+    fn test_insert_triggers_growth() {
+        let mut list = nl();
+        // Fill to capacity (assuming initial capacity is 0, grows to 1, then 2, etc.)
+        list.push(1); // cap = 1
+        list.push(2); // cap = 2
+        list.push(3); // cap = 4
+        list.push(4);
+        let initial_cap = list.cap();
+        list.insert(1, 5); // Should trigger growth if cap is full
+        assert_eq!(list.get(0), Some(&1), "First element should remain");
+        assert_eq!(list.get(1), Some(&5), "Inserted element should be at index 1");
+        assert_eq!(list.get(2), Some(&2), "Second element should be shifted");
+        assert_eq!(list.get(3), Some(&3), "Third element should be shifted");
+        assert_eq!(list.get(4), Some(&4), "Third element should be shifted");
+        assert_eq!(list.len, 5, "Length should be 4 after insert");
+        assert!(list.cap() > initial_cap, "Capacity should increase after insert");
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_insert_out_of_bounds() {
+        let mut list = nl();
+        list.push(1);
+        list.insert(2, 2); // Should panic since index 2 > len
+    }
+
+    #[test]
+    fn test_multiple_inserts() {
+        let mut list = nl();
+        list.push(2);
+        list.push(4);
+        list.insert(0, 1);
+        list.insert(2, 3);
+        list.insert(4, 5);
+        assert_eq!(list.get(0), Some(&1), "First insert should be at index 0");
+        assert_eq!(list.get(1), Some(&2), "Original first element shifted");
+        assert_eq!(list.get(2), Some(&3), "Second insert should be at index 2");
+        assert_eq!(list.get(3), Some(&4), "Original second element shifted");
+        assert_eq!(list.get(4), Some(&5), "Third insert should be at index 4");
+        assert_eq!(list.len, 5, "Length should be 5 after inserts");
+    }
+
+    #[test]
+    fn test_insert_with_deref() {
+        let mut list = nl();
+        list.push(1);
+        list.push(3);
+        list.insert(1, 2);
+        let slice: &[i32] = &list;
+        assert_eq!(slice, &[1, 2, 3], "Deref should return correct slice after insert");
+    }
+
+    #[test]
+    fn test_remove_from_single_element_list() {
+        let mut list = nl();
+        list.push(42);
+        let removed = list.remove(0);
+        assert_eq!(removed, 42, "Remove should return the removed element");
+        assert_eq!(list.len, 0, "Length should be 0 after remove");
+        assert_eq!(list.get(0), None, "List should be empty after remove");
+    }
+
+    #[test]
+    fn test_remove_from_start() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let removed = list.remove(0);
+        assert_eq!(removed, 1, "Remove should return first element");
+        assert_eq!(list.get(0), Some(&2), "Second element should shift to index 0");
+        assert_eq!(list.get(1), Some(&3), "Third element should shift to index 1");
+        assert_eq!(list.len, 2, "Length should be 2 after remove");
+    }
+
+    #[test]
+    fn test_remove_from_end() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let removed = list.remove(2);
+        assert_eq!(removed, 3, "Remove should return last element");
+        assert_eq!(list.get(0), Some(&1), "First element should remain");
+        assert_eq!(list.get(1), Some(&2), "Second element should remain");
+        assert_eq!(list.get(2), None, "Index 2 should be out of bounds");
+        assert_eq!(list.len, 2, "Length should be 2 after remove");
+    }
+
+    #[test]
+    fn test_remove_from_middle() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let removed = list.remove(1);
+        assert_eq!(removed, 2, "Remove should return middle element");
+        assert_eq!(list.get(0), Some(&1), "First element should remain");
+        assert_eq!(list.get(1), Some(&3), "Third element should shift to index 1");
+        assert_eq!(list.get(2), None, "Index 2 should be out of bounds");
+        assert_eq!(list.len, 2, "Length should be 2 after remove");
+    }
+
+    #[test]
+    fn test_remove_with_strings() {
+        let mut list: List<String> = nl();
+        list.push(String::from("a"));
+        list.push(String::from("b"));
+        list.push(String::from("c"));
+        let removed = list.remove(1);
+        assert_eq!(removed, String::from("b"), "Remove should return middle string");
+        assert_eq!(list.get(0), Some(&String::from("a")), "First string should remain");
+        assert_eq!(list.get(1), Some(&String::from("c")), "Third string should shift to index 1");
+        assert_eq!(list.len, 2, "Length should be 2 after remove");
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_remove_out_of_bounds() {
+        let mut list = nl();
+        list.push(1);
+        list.remove(1); // Should panic since index 1 >= len
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_remove_from_empty_list() {
+        let mut list: List<i32> = nl();
+        list.remove(0); // Should panic since list is empty
+    }
+
+    #[test]
+    fn test_multiple_removes() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.push(4);
+        let removed1 = list.remove(0);
+        let removed2 = list.remove(1);
+        assert_eq!(removed1, 1, "First remove should return 1");
+        assert_eq!(removed2, 3, "Second remove should return 3");
+        assert_eq!(list.get(0), Some(&2), "Second element should be at index 0");
+        assert_eq!(list.get(1), Some(&4), "Fourth element should be at index 1");
+        assert_eq!(list.len, 2, "Length should be 2 after removes");
+    }
+
+    #[test]
+    fn test_remove_with_deref() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.remove(1);
+        let slice: &[i32] = &list;
+        assert_eq!(slice, &[1, 3], "Deref should return correct slice after remove");
+    }
+
+    #[test]
+    fn test_remove_preserves_capacity() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        let initial_cap = list.cap();
+        list.remove(0);
+        assert_eq!(list.cap(), initial_cap, "Capacity should not change after remove");
+        assert_eq!(list.len, 1, "Length should be 1 after remove");
+        assert_eq!(list.get(0), Some(&2), "Remaining element should be correct");
+    }
+
+    #[test]
+    fn test_into_iter_empty_list() {
+        let list: List<i32> = nl();
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next(), None, "Empty iterator should yield None");
+        assert_eq!(iter.next_back(), None, "Empty iterator should yield None for next_back");
+        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) for empty iterator");
+    }
+
+    #[test]
+    fn test_into_iter_single_element() {
+        let mut list = nl();
+        list.push(42);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.size_hint(), (1, Some(1)), "Size hint should be (1, Some(1))");
+        assert_eq!(iter.next(), Some(42), "Iterator should yield single element");
+        assert_eq!(iter.next(), None, "Iterator should be exhausted after one element");
+        assert_eq!(iter.next_back(), None, "Exhausted iterator should yield None for next_back");
+        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after exhaustion");
+    }
+
+    #[test]
+    fn test_into_iter_multiple_elements_forward() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.size_hint(), (3, Some(3)), "Size hint should be (3, Some(3))");
+        assert_eq!(iter.next(), Some(1), "First element should be 1");
+        assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should update after next");
+        assert_eq!(iter.next(), Some(2), "Second element should be 2");
+        assert_eq!(iter.next(), Some(3), "Third element should be 3");
+        assert_eq!(iter.next(), None, "Iterator should be exhausted");
+        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after exhaustion");
+    }
+
+    #[test]
+    fn test_into_iter_multiple_elements_backward() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next_back(), Some(3), "Last element should be 3");
+        assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should update after next_back");
+        assert_eq!(iter.next_back(), Some(2), "Second-to-last element should be 2");
+        assert_eq!(iter.next_back(), Some(1), "First element should be 1");
+        assert_eq!(iter.next_back(), None, "Iterator should be exhausted");
+        assert_eq!(iter.next(), None, "Exhausted iterator should yield None for next");
+    }
+
+    #[test]
+    fn test_into_iter_mixed_forward_backward() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.next(), Some(1), "First element should be 1");
+        assert_eq!(iter.next_back(), Some(3), "Last element should be 3");
+        assert_eq!(iter.size_hint(), (1, Some(1)), "Size hint should be (1, Some(1))");
+        assert_eq!(iter.next(), Some(2), "Middle element should be 2");
+        assert_eq!(iter.next(), None, "Iterator should be exhausted");
+        assert_eq!(iter.next_back(), None, "Exhausted iterator should yield None");
+    }
+
+    #[test]
+    fn test_into_iter_with_strings() {
+        let mut list: List<String> = nl();
+        list.push(String::from("a"));
+        list.push(String::from("b"));
+        let mut iter = list.into_iter();
+        assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should be (2, Some(2))");
+        assert_eq!(iter.next(), Some(String::from("a")), "First element should be 'a'");
+        assert_eq!(iter.next_back(), Some(String::from("b")), "Last element should be 'b'");
+        assert_eq!(iter.next(), None, "Iterator should be exhausted");
+    }
+
+    #[test]
+    fn test_into_iter_for_loop() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let mut result = Vec::new();
+        for x in list {
+            result.push(x);
+        }
+        assert_eq!(result, vec![1, 2, 3], "For loop should yield elements in order");
+    }
+
+    #[test]
+    fn test_into_iter_collect() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let result: Vec<i32> = list.into_iter().collect();
+        assert_eq!(result, vec![1, 2, 3], "Collect should yield elements in order");
+    }
+
+    #[test]
+    fn test_into_iter_drop_early() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        {
+            let mut iter = list.into_iter();
+            assert_eq!(iter.next(), Some(1), "First element should be 1");
+            // Drop iterator early (before consuming all elements)
+        }
+        // No assertion needed; test passes if no memory leaks or panics occur
+    }
+
+    #[test]
+    fn test_into_iter_size_hint_edge_cases() {
+        let mut list = nl();
+        list.push(1);
+        let mut iter = list.into_iter();
+        assert_eq!(iter.size_hint(), (1, Some(1)), "Initial size hint should be (1, Some(1))");
+        iter.next();
+        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after consuming");
+        iter.next_back();
+        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should remain (0, Some(0)) after exhaustion");
+    }
+
+    #[test]
+    fn test_drain_empty_list() {
+        let mut list: List<i32> = nl();
+        {
+            let mut iter = list.drain(..);
+            assert_eq!(iter.next(), None, "Empty iterator should yield None");
+            assert_eq!(iter.next_back(), None, "Empty iterator should yield None for next_back");
+            assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) for empty iterator");
+        }
+        list.push(3);
+        assert_eq!(list.len, 1);
+    }
+
+    #[test]
+    fn test_drain_single_element() {
+        let mut list = nl();
+        {
+            list.push(42);
+            let mut iter = list.drain(..);
+            assert_eq!(iter.size_hint(), (1, Some(1)), "Size hint should be (1, Some(1))");
+            assert_eq!(iter.next(), Some(42), "Iterator should yield single element");
+            assert_eq!(iter.next(), None, "Iterator should be exhausted after one element");
+            assert_eq!(iter.next_back(), None, "Exhausted iterator should yield None for next_back");
+            assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after exhaustion");
+        }
+        list.push(3);
+        assert_eq!(list.len, 1);
+    }
+
+    #[test]
+    fn test_drain_multiple_elements_forward() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        {
+            let mut iter = list.drain(..);
+            assert_eq!(iter.size_hint(), (3, Some(3)), "Size hint should be (3, Some(3))");
+            assert_eq!(iter.next(), Some(1), "First element should be 1");
+            assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should update after next");
+            assert_eq!(iter.next(), Some(2), "Second element should be 2");
+            assert_eq!(iter.next(), Some(3), "Third element should be 3");
+            assert_eq!(iter.next(), None, "Iterator should be exhausted");
+            assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after exhaustion");
+        }
+        list.push(3);
+        assert_eq!(list.len, 1);
+    }
+
+    #[test]
+    fn test_drain_multiple_elements_backward() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        {
+            let mut iter = list.drain(..);
+            assert_eq!(iter.next_back(), Some(3), "Last element should be 3");
+            assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should update after next_back");
+            assert_eq!(iter.next_back(), Some(2), "Second-to-last element should be 2");
+            assert_eq!(iter.next_back(), Some(1), "First element should be 1");
+            assert_eq!(iter.next_back(), None, "Iterator should be exhausted");
+            assert_eq!(iter.next(), None, "Exhausted iterator should yield None for next");
+        }
+        list.push(3);
+        assert_eq!(list.len, 1);
+    }
+
+    #[test]
+    fn test_drain_mixed_forward_backward() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        {
+            let mut iter = list.drain(..);
+            assert_eq!(iter.next(), Some(1), "First element should be 1");
+            assert_eq!(iter.next_back(), Some(3), "Last element should be 3");
+            assert_eq!(iter.size_hint(), (1, Some(1)), "Size hint should be (1, Some(1))");
+            assert_eq!(iter.next(), Some(2), "Middle element should be 2");
+            assert_eq!(iter.next(), None, "Iterator should be exhausted");
+            assert_eq!(iter.next_back(), None, "Exhausted iterator should yield None");
+        }
+        list.push(3);
+        assert_eq!(list.len, 1);
+    }
+
+    #[test]
+    fn test_drain_with_strings() {
+        let mut list: List<String> = nl();
+        list.push(String::from("a"));
+        list.push(String::from("b"));
+        {
+            let mut iter = list.drain(..);
+            assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should be (2, Some(2))");
+            assert_eq!(iter.next(), Some(String::from("a")), "First element should be 'a'");
+            assert_eq!(iter.next_back(), Some(String::from("b")), "Last element should be 'b'");
+            assert_eq!(iter.next(), None, "Iterator should be exhausted");
+        }
+
+        list.push(String::from("c"));
+        assert_eq!(list.len, 1);
+    }
+
+    #[test]
+    fn test_drain_collect() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let result: Vec<i32> = list.drain(..).collect();
+        assert_eq!(result, vec![1, 2, 3], "Collect should yield elements in order");
+        list.push(1);
+        assert_eq!(list.len, 1);
+    }
+
+    #[test]
+    fn test_drain_drop_early() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        {
+            let mut iter = list.drain(..);
+            assert_eq!(iter.next(), Some(1), "First element should be 1");
+            // Drop iterator early (before consuming all elements)
+        }
+        // No assertion needed; test passes if no memory leaks or panics occur
+    }
+
+    #[test]
+    fn test_drain_size_hint_edge_cases() {
+        let mut list = nl();
+        list.push(1);
+        let mut iter = list.drain(..);
+        assert_eq!(iter.size_hint(), (1, Some(1)), "Initial size hint should be (1, Some(1))");
+        iter.next();
+        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after consuming");
+        iter.next_back();
+        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should remain (0, Some(0)) after exhaustion");
+    }
+
+    #[test]
+    fn test_truncate_to_smaller_length() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.push(4);
+        list.push(5);
+        list.truncate(3);
+        assert_eq!(list.len, 3, "Length should be 3 after truncate");
+        assert_eq!(list.get(0), Some(&1), "First element should remain");
+        assert_eq!(list.get(1), Some(&2), "Second element should remain");
+        assert_eq!(list.get(2), Some(&3), "Third element should remain");
+        assert_eq!(list.get(3), None, "Fourth element should be discarded");
+        assert_eq!(list.get(4), None, "Fifth element should be discarded");
+    }
+
+    #[test]
+    fn test_truncate_to_zero() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.truncate(0);
+        assert_eq!(list.len, 0, "Length should be 0 after truncate to 0");
+        assert_eq!(list.get(0), None, "List should be empty after truncate to 0");
+    }
+
+    #[test]
+    fn test_truncate_to_current_length() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.truncate(3);
+        assert_eq!(list.len, 3, "Length should remain 3");
+        assert_eq!(list.get(0), Some(&1), "First element should remain");
+        assert_eq!(list.get(1), Some(&2), "Second element should remain");
+        assert_eq!(list.get(2), Some(&3), "Third element should remain");
+    }
+
+    #[test]
+    fn test_truncate_to_larger_length() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.truncate(10);
+        assert_eq!(list.len, 3, "Length should remain 3");
+        assert_eq!(list.get(0), Some(&1), "First element should remain");
+        assert_eq!(list.get(1), Some(&2), "Second element should remain");
+        assert_eq!(list.get(2), Some(&3), "Third element should remain");
+    }
+
+    #[test]
+    fn test_truncate_empty_list() {
+        let mut list: List<i32> = nl();
+        list.truncate(0);
+        assert_eq!(list.len, 0, "Length should be 0");
+        list.truncate(5);
+        assert_eq!(list.len, 0, "Length should remain 0");
+    }
+
+    #[test]
+    fn test_truncate_with_strings() {
+        let mut list: List<String> = nl();
+        list.push(String::from("a"));
+        list.push(String::from("b"));
+        list.push(String::from("c"));
+        list.push(String::from("d"));
+        list.truncate(2);
+        assert_eq!(list.len, 2, "Length should be 2 after truncate");
+        assert_eq!(list.get(0), Some(&String::from("a")), "First string should remain");
+        assert_eq!(list.get(1), Some(&String::from("b")), "Second string should remain");
+        assert_eq!(list.get(2), None, "Third string should be discarded");
+        // No assertion needed; test passes if no memory leaks occur
+    }
+
+    #[test]
+    fn test_truncate_preserves_capacity() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.push(4);
+        let initial_cap = list.cap();
+        list.truncate(2);
+        assert_eq!(list.cap(), initial_cap, "Capacity should not change after truncate");
+        assert_eq!(list.len, 2, "Length should be 2 after truncate");
+    }
+
+    #[test]
+    fn test_truncate_then_push() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.truncate(1);
+        assert_eq!(list.len, 1, "Length should be 1 after truncate");
+        list.push(4);
+        assert_eq!(list.len, 2, "Length should be 2 after push");
+        assert_eq!(list.get(0), Some(&1), "First element should be 1");
+        assert_eq!(list.get(1), Some(&4), "Second element should be 4");
+    }
+
+    #[test]
+    fn test_truncate_with_deref() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.push(4);
+        list.truncate(2);
+        let slice: &[i32] = &list;
+        assert_eq!(slice, &[1, 2], "Deref should return correct slice after truncate");
+    }
+
+    #[test]
+    fn test_retain_split_by_even() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.push(4);
+        let removed = list.retain_split(|x| x % 2 == 0);
+        let kept: &[i32] = &list;
+        let removed: &[i32] = &removed;
+        assert_eq!(kept, &[2, 4], "Even elements should be kept in order");
+        assert_eq!(removed, &[1, 3], "Odd elements should be returned in order");
+    }
+
+    #[test]
+    fn test_retain_split_panic_keeps_unvisited_tail() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for _ in 0..5 {
+            list.push(DropCounter(&drops));
+        }
+        let mut seen = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.retain_split(|_| {
+                seen += 1;
+                if seen == 3 {
+                    panic!("predicate panicked");
+                }
+                seen % 2 == 0
+            })
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 1, "The element already split off should be dropped with the new list");
+        assert_eq!(list.len(), 4, "Unvisited elements should stay in the list after a panic");
+        drop(list);
+        assert_eq!(drops.get(), 5, "Every element should be dropped exactly once");
+    }
+
+    #[test]
+    fn test_truncate_and_shrink_drops_tail_and_reallocates() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for _ in 0..4 {
+            list.push(DropCounter(&drops));
+        }
+        assert_eq!(list.cap(), 4, "Capacity should be 4 after four pushes");
+
+        let reg = Region::new(GLOBAL);
+        list.truncate_and_shrink(1);
+        let change = reg.change();
+
+        assert_eq!(drops.get(), 3, "Truncated elements should be dropped");
+        assert_eq!(list.len, 1, "Length should be 1 after truncate_and_shrink");
+        assert_eq!(list.cap(), 1, "Capacity should shrink to the new length");
+        assert!(change.reallocations >= 1, "Shrinking should reallocate the buffer");
+        drop(list);
+        assert_eq!(drops.get(), 4, "Remaining element should be dropped with the list");
+    }
+
+    #[test]
+    fn test_truncate_and_shrink_past_len_only_shrinks() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for _ in 0..3 {
+            list.push(DropCounter(&drops));
+        }
+        assert_eq!(list.cap(), 4, "Capacity should be 4 after three pushes");
+        list.truncate_and_shrink(5);
+        assert_eq!(drops.get(), 0, "No elements should be dropped");
+        assert_eq!(list.len, 3, "Length should remain 3");
+        assert_eq!(list.cap(), 3, "Capacity should still shrink to the length");
+    }
+
+    #[test]
+    fn test_get_or_insert_with_extends_empty_list() {
+        let mut list = nl();
+        let mut calls = 0;
+        *list.get_or_insert_with(5, || { calls += 1; 0 }) += 7;
+        assert_eq!(calls, 6, "f should be called once for every missing slot");
+        let slice: &[i32] = &list;
+        assert_eq!(slice, &[0, 0, 0, 0, 0, 7], "Intervening slots should be filled by f");
+    }
+
+    #[test]
+    fn test_get_or_insert_with_existing_index() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        *list.get_or_insert_with(1, || panic!("f should not be called")) = 5;
+        let slice: &[i32] = &list;
+        assert_eq!(slice, &[1, 5], "Existing element should be returned");
+    }
+
+    #[test]
+    fn test_split_first_owned() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        list.push((1, DropCounter(&drops)));
+        list.push((2, DropCounter(&drops)));
+        list.push((3, DropCounter(&drops)));
+        let (first, rest) = list.split_first_owned().expect("List should not be empty");
+        assert_eq!(first.0, 1, "First element should be split off");
+        assert_eq!(rest.len, 2, "Remaining list should have 2 elements");
+        assert_eq!(rest[0].0, 2, "Remaining list should start at the second element");
+        assert_eq!(rest[1].0, 3, "Remaining list should keep its order");
+        assert_eq!(drops.get(), 0, "Nothing should be dropped by the split");
+        drop(first);
+        drop(rest);
+        assert_eq!(drops.get(), 3, "Every element should be dropped exactly once");
+    }
+
+    #[test]
+    fn test_split_last_owned() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        list.push((1, DropCounter(&drops)));
+        list.push((2, DropCounter(&drops)));
+        list.push((3, DropCounter(&drops)));
+        let (last, rest) = list.split_last_owned().expect("List should not be empty");
+        assert_eq!(last.0, 3, "Last element should be split off");
+        assert_eq!(rest.len, 2, "Remaining list should have 2 elements");
+        assert_eq!(rest[0].0, 1, "Remaining list should keep its order");
+        assert_eq!(rest[1].0, 2, "Remaining list should keep its order");
+        assert_eq!(drops.get(), 0, "Nothing should be dropped by the split");
+        drop(last);
+        drop(rest);
+        assert_eq!(drops.get(), 3, "Every element should be dropped exactly once");
+    }
+
+    #[test]
+    fn test_split_owned_empty_list() {
+        let drops = Cell::new(0);
+        let list: List<DropCounter> = nl();
+        assert!(list.split_first_owned().is_none(), "Empty list should not split");
+        let list: List<DropCounter> = nl();
+        assert!(list.split_last_owned().is_none(), "Empty list should not split");
+        assert_eq!(drops.get(), 0, "Nothing should be dropped");
+    }
+
+    #[test]
+    fn test_as_slice_and_as_mut_slice() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.as_slice(), &[1, 2, 3], "as_slice should view every element");
+        list.as_mut_slice()[1] = 5;
+        list.as_mut_slice().reverse();
+        assert_eq!(list.as_slice(), &[3, 5, 1], "as_mut_slice should allow in-place edits");
+    }
+
+    #[test]
+    fn test_dedup_by_reporting_count_matches_shrinkage() {
+        let mut list = nl();
+        for x in [1, 1, 2, 3, 3, 3, 1, 4, 4] {
+            list.push(x);
+        }
+        let before = list.len;
+        let removed = list.dedup_by_reporting(|a, b| a == b);
+        assert_eq!(removed, before - list.len, "Reported count should match the change in length");
+        assert_eq!(removed, 4, "Four duplicates should be removed");
+        assert_eq!(list.as_slice(), &[1, 2, 3, 1, 4], "Only consecutive duplicates should be removed");
+    }
+
+    #[test]
+    fn test_dedup_by_reporting_no_duplicates() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.dedup_by_reporting(|a, b| a == b), 0, "Nothing should be removed");
+        let mut empty: List<i32> = nl();
+        assert_eq!(empty.dedup_by_reporting(|a, b| a == b), 0, "Empty list has nothing to remove");
+    }
+
+    #[test]
+    fn test_dedup_variants_drop_removed_elements() {
+        let mut list: List<String> = nl();
+        for s in ["a", "a", "b", "B", "c"] {
+            list.push(String::from(s));
+        }
+        list.dedup();
+        assert_eq!(list.len, 4, "dedup should remove the repeated 'a'");
+        list.dedup_by_key(|s| s.to_lowercase());
+        assert_eq!(list.len, 3, "dedup_by_key should remove 'B'");
+        list.dedup_by(|_, _| true);
+        assert_eq!(list.as_slice(), &[String::from("a")], "dedup_by should keep the first of the run");
+    }
+
+    #[test]
+    fn test_shrink_if_oversized_at_trigger_boundary() {
+        let mut list = nl();
+        for i in 0..16 {
+            list.push(i);
+        }
+        assert_eq!(list.cap(), 16);
+        list.truncate(4);
+        list.shrink_if_oversized(4.0);
+        assert_eq!(list.cap(), 16, "Exactly a quarter full should not shrink");
+        list.truncate(3);
+        list.shrink_if_oversized(4.0);
+        assert_eq!(list.cap(), 3, "Less than a quarter full should shrink to len");
+        assert_eq!(list.as_slice(), &[0, 1, 2], "Shrinking should keep the elements");
+    }
+
+    #[test]
+    fn test_shrink_if_oversized_just_above_trigger() {
+        let mut list = nl();
+        for i in 0..16 {
+            list.push(i);
+        }
+        list.truncate(5);
+        list.shrink_if_oversized(4.0);
+        assert_eq!(list.cap(), 16, "More than a quarter full should not shrink");
+        list.truncate(0);
+        list.shrink_if_oversized(4.0);
+        assert_eq!(list.cap(), 0, "Empty list should release its buffer");
+        list.push(1);
+        assert_eq!(list.as_slice(), &[1], "List should still be usable after releasing the buffer");
+    }
+
+    #[test]
+    #[should_panic(expected = "shrink ratio must be finite and at least 1.0, got 0.5")]
+    fn test_shrink_if_oversized_rejects_small_ratio() {
+        let mut list = list![1];
+        list.shrink_if_oversized(0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "shrink ratio must be finite and at least 1.0, got NaN")]
+    fn test_shrink_if_oversized_rejects_nan() {
+        let mut list = list![1];
+        list.shrink_if_oversized(f64::NAN);
+    }
+
+    #[test]
+    fn test_rchunks_owned() {
+        let mut list = nl();
+        for i in 1..=7 {
+            list.push(i);
+        }
+        let chunks: Vec<Vec<i32>> = list.rchunks_owned(3).map(|c| c.into_iter().collect()).collect();
+        assert_eq!(chunks, vec![vec![5, 6, 7], vec![2, 3, 4], vec![1]], "Chunks should be taken from the end");
+    }
+
+    #[test]
+    fn test_rchunks_owned_drops_unyielded_chunks() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for _ in 0..5 {
+            list.push(DropCounter(&drops));
+        }
+        let mut chunks = list.rchunks_owned(2);
+        let first = chunks.next().expect("Should yield a chunk");
+        assert_eq!(first.len, 2, "First chunk should be full size");
+        drop(chunks);
+        assert_eq!(drops.get(), 3, "Remaining elements should drop with the iterator");
+        drop(first);
+        assert_eq!(drops.get(), 5, "Every element should be dropped exactly once");
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_rchunks_owned_zero_size() {
+        let list: List<i32> = nl();
+        let _ = list.rchunks_owned(0);
+    }
+
+    #[test]
+    fn test_dedup_total_by_key() {
+        let mut list: List<(u32, String)> = nl();
+        list.push((1, String::from("a")));
+        list.push((2, String::from("b")));
+        list.push((1, String::from("c")));
+        list.push((3, String::from("d")));
+        list.push((2, String::from("e")));
+        list.dedup_total_by_key(|record| record.0);
+        let names: Vec<&str> = list.iter().map(|record| &record.1[..]).collect();
+        assert_eq!(names, vec!["a", "b", "d"], "First occurrence of each id should be kept in order");
+    }
+
+    #[test]
+    fn test_dedup_total_by_key_drops_discarded_records() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for id in [1, 2, 1, 1, 3, 2] {
+            list.push((id, DropCounter(&drops)));
+        }
+        list.dedup_total_by_key(|record| record.0);
+        assert_eq!(drops.get(), 3, "Discarded records should be dropped");
+        assert_eq!(list.len, 3, "One record per id should remain");
+        drop(list);
+        assert_eq!(drops.get(), 6, "Every record should be dropped exactly once");
+    }
+
+    #[test]
+    fn test_into_iter_rev_collect() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        let result: Vec<i32> = list.into_iter_rev().collect();
+        assert_eq!(result, vec![3, 2, 1], "Elements should be yielded last to first");
+    }
+
+    #[test]
+    fn test_into_iter_rev_drop_early() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for i in 0..4 {
+            list.push((i, DropCounter(&drops)));
+        }
+        let mut iter = list.into_iter_rev();
+        let last = iter.next().expect("Should yield the last element");
+        assert_eq!(last.0, 3, "First yielded element should be the last one");
+        drop(iter);
+        assert_eq!(drops.get(), 3, "Un-yielded elements should be dropped with the iterator");
+        drop(last);
+        assert_eq!(drops.get(), 4, "Every element should be dropped exactly once");
+    }
+
+    #[test]
+    fn test_allocated_bytes_follows_capacity() {
+        let mut list: List<u64> = nl();
+        assert_eq!(list.allocated_bytes(), 0, "New list should not allocate");
+        list.push(1);
+        assert_eq!(list.allocated_bytes(), 8, "Capacity 1 should be 8 bytes");
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.allocated_bytes(), 32, "Capacity 4 should be 32 bytes");
+        list.truncate_and_shrink(1);
+        assert_eq!(list.allocated_bytes(), 8, "Shrinking should release bytes");
+    }
+
+    #[test]
+    fn test_prepend_iter_keeps_iterator_order() {
+        let mut list = nl();
+        list.push(10);
+        list.push(11);
+        list.prepend_iter((1..8).filter(|x| x % 2 == 1));
+        assert_eq!(list.as_slice(), &[1, 3, 5, 7, 10, 11], "Batch should be inserted in order before the old elements");
+        list.prepend_iter(std::iter::empty());
+        assert_eq!(list.len, 6, "Prepending nothing should not change the list");
+    }
+
+    #[test]
+    fn test_prepend_iter_with_strings() {
+        let mut list: List<String> = nl();
+        list.push(String::from("c"));
+        list.prepend_iter(["a", "b"].iter().map(|s| String::from(*s)));
+        assert_eq!(list.as_slice(), &[String::from("a"), String::from("b"), String::from("c")]);
+    }
+
+    #[test]
+    fn test_with_capacity_avoids_growth() {
+        let mut list = List::with_capacity(10);
+        assert_eq!(list.cap(), 10, "Capacity should be allocated up front");
+        assert_eq!(list.len, 0, "List should start empty");
+        for i in 0..10 {
+            list.push(i);
+        }
+        assert_eq!(list.cap(), 10, "Pushing up to capacity should not grow");
+        list.push(10);
+        assert_eq!(list.cap(), 20, "Pushing past capacity should grow as usual");
+    }
+
+    #[test]
+    fn test_with_capacity_zero_behaves_like_new() {
+        let mut list: List<i32> = List::with_capacity(0);
+        assert_eq!(list.cap(), 0, "Zero capacity should not allocate");
+        list.push(1);
+        assert_eq!(list.as_slice(), &[1]);
+    }
+
+    #[test]
+    fn test_capacity_reports_allocated_space() {
+        let mut list = nl();
+        assert_eq!(list.capacity(), 0, "New list should have no capacity");
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.capacity(), 4, "Capacity should double as the list grows");
+        assert_eq!(list.len(), 3, "Length should be tracked separately from capacity");
+        list.pop();
+        assert_eq!(list.capacity(), 4, "Popping should not change capacity");
+    }
+
+    #[test]
+    fn test_rotated_left() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for i in 1..=5 {
+            list.push((i, DropCounter(&drops)));
+        }
+        let rotated = list.rotated_left(2);
+        let order: Vec<i32> = rotated.iter().map(|e| e.0).collect();
+        assert_eq!(order, vec![3, 4, 5, 1, 2], "Elements should be rotated left by 2");
+        assert_eq!(drops.get(), 0, "Rotating should not drop anything");
+        drop(rotated);
+        assert_eq!(drops.get(), 5, "Every element should be dropped exactly once");
+    }
+
+    #[test]
+    fn test_rotated_right() {
+        let mut list = nl();
+        for i in 1..=5 {
+            list.push(i);
+        }
+        let rotated = list.rotated_right(2);
+        assert_eq!(rotated.as_slice(), &[4, 5, 1, 2, 3], "Elements should be rotated right by 2");
+        let rotated = rotated.rotated_right(0).rotated_left(5);
+        assert_eq!(rotated.as_slice(), &[4, 5, 1, 2, 3], "Full rotations should be no-ops");
+    }
+
+    #[test]
+    #[should_panic(expected = "mid out of bounds")]
+    fn test_rotated_left_out_of_bounds() {
+        let mut list = nl();
+        list.push(1);
+        let _ = list.rotated_left(2);
+    }
+
+    #[test]
+    fn test_reserve_grows_once() {
+        let mut list = nl();
+        list.reserve(100);
+        let cap = list.capacity();
+        assert!(cap >= 100, "Capacity should fit the reserved elements");
+        for i in 0..100 {
+            list.push(i);
+        }
+        assert_eq!(list.capacity(), cap, "Pushing reserved elements should not reallocate");
+        list.reserve(0);
+        assert_eq!(list.capacity(), cap, "Reserving nothing should be a no-op");
+    }
+
+    #[test]
+    fn test_reserve_is_noop_with_enough_room() {
+        let mut list = List::with_capacity(8);
+        list.push(1);
+        list.reserve(7);
+        assert_eq!(list.capacity(), 8, "Reserve should not grow when there is room");
+        list.reserve(8);
+        assert!(list.capacity() >= 9, "Reserve should grow when there isn't room");
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_reserve_overflow() {
+        let mut list = nl();
+        list.push(1);
+        list.reserve(usize::MAX);
+    }
+
+    #[test]
+    fn test_reserve_exact_on_empty_list() {
+        let mut list = nl();
+        list.reserve_exact(7);
+        assert_eq!(list.capacity(), 7, "Capacity should be exactly what was reserved");
+        for i in 0..7 {
+            list.push(i);
+        }
+        assert_eq!(list.capacity(), 7, "Pushing reserved elements should not reallocate");
+    }
+
+    #[test]
+    fn test_reserve_exact_does_not_round_up() {
+        let mut list = nl();
+        list.push(1);
+        list.push(2);
+        list.push(3);
+        list.reserve_exact(1);
+        assert_eq!(list.capacity(), 4, "Reserve should be a no-op when there is room");
+        list.reserve_exact(10);
+        assert_eq!(list.capacity(), 13, "Capacity should be len + additional");
+        assert_eq!(list.as_slice(), &[1, 2, 3], "Elements should survive the reallocation");
+    }
+
+    #[test]
+    fn test_dedup_window() {
+        let mut list = nl();
+        for x in [1, 2, 1, 1, 3, 1] {
+            list.push(x);
+        }
+        list.dedup_window(2);
+        assert_eq!(list.as_slice(), &[1, 2, 3, 1], "Only repeats within the last 2 kept elements should be removed");
+    }
+
+    #[test]
+    fn test_dedup_window_with_strings() {
+        let mut list: List<String> = nl();
+        for s in ["a", "b", "c", "a", "c"] {
+            list.push(String::from(s));
+        }
+        list.dedup_window(0);
+        assert_eq!(list.len, 5, "A zero window should not remove anything");
+        list.dedup_window(1);
+        assert_eq!(list.len, 5, "No consecutive repeats to remove");
+        list.dedup_window(3);
+        let kept: Vec<&str> = list.iter().map(|s| &s[..]).collect();
+        assert_eq!(kept, vec!["a", "b", "c"], "Repeats within the last 3 kept should be removed");
     }
 
     #[test]
-    fn test_multiple_operations() {
+    fn test_dedup_window_panicking_eq_keeps_unvisited_tail() {
+        // Every value hashes the same, so each comparison reaches `eq`
+        struct PanicOnEq<'a> {
+            drops: &'a Cell<usize>,
+            eqs: &'a Cell<usize>,
+        }
+        impl <'a> PartialEq for PanicOnEq<'a> {
+            fn eq(&self, _: &Self) -> bool {
+                self.eqs.set(self.eqs.get() + 1);
+                assert!(self.eqs.get() < 3, "eq panicked");
+                true
+            }
+        }
+        impl <'a> Eq for PanicOnEq<'a> {}
+        impl <'a> Hash for PanicOnEq<'a> {
+            fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+        }
+        impl <'a> Drop for PanicOnEq<'a> {
+            fn drop(&mut self) {
+                self.drops.set(self.drops.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let eqs = Cell::new(0);
         let mut list = nl();
-        list.push(1);
-        list.push(2);
-        assert_eq!(list.get(0), Some(&1), "Get index 0 should return 1");
-        assert_eq!(list.pop(), Some(2), "Pop should return 2");
-        list.push(3);
-        assert_eq!(list[0], 1, "Index 0 should still return 1");
-        assert_eq!(list.get(1), Some(&3), "Get index 1 should return 3");
-        assert_eq!(list.pop(), Some(3), "Pop should return 3");
-        assert_eq!(list.pop(), Some(1), "Pop should return 1");
-        assert_eq!(list.pop(), None, "Pop on empty list should return None");
+        for _ in 0..5 {
+            list.push(PanicOnEq { drops: &drops, eqs: &eqs });
+        }
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.dedup_window(2);
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 2, "Only the repeats found before the panic should be dropped");
+        assert_eq!(list.len(), 3, "Unvisited elements should stay in the list after a panic");
+        drop(list);
+        assert_eq!(drops.get(), 5, "Every element should be dropped exactly once");
     }
 
     #[test]
-    fn test_push_pop_empty() {
+    fn test_try_reserve_succeeds() {
         let mut list = nl();
-        assert_eq!(list.pop(), None, "Pop on empty list should return None");
         list.push(1);
+        assert_eq!(list.try_reserve(10), Ok(()), "Reasonable reservation should succeed");
+        assert!(list.capacity() >= 11, "Capacity should fit the reserved elements");
+        assert_eq!(list.as_slice(), &[1], "Elements should survive the reservation");
+    }
+
+    #[test]
+    fn test_try_reserve_reports_capacity_overflow() {
+        let mut list: List<u64> = nl();
+        list.push(1);
+        assert_eq!(
+            list.try_reserve(isize::MAX as usize / 4),
+            Err(TryReserveError::CapacityOverflow),
+            "More than isize::MAX bytes should be a capacity overflow"
+        );
+        assert_eq!(
+            list.try_reserve(usize::MAX),
+            Err(TryReserveError::CapacityOverflow),
+            "Overflowing len + additional should be a capacity overflow"
+        );
+        assert_eq!(list.as_slice(), &[1], "Failed reservation should leave the list untouched");
+        assert_eq!(list.capacity(), 1, "Failed reservation should not change capacity");
+    }
+
+    #[test]
+    fn test_try_from_iter_collects_all_ok() {
+        let list: Result<List<i32>, String> = List::try_from_iter(vec![Ok(1), Ok(2), Ok(3)]);
+        let list = list.expect("All items were Ok");
+        assert_eq!(list.as_slice(), &[1, 2, 3], "Items should be collected in order");
+    }
+
+    #[test]
+    fn test_try_from_iter_stops_at_first_error() {
+        let drops = Cell::new(0);
+        let mut produced = 0;
+        let items = (0..5).map(|i| {
+            produced += 1;
+            if i == 2 { Err(format!("bad item {}", i)) } else { Ok(DropCounter(&drops)) }
+        });
+        let result = List::try_from_iter(items);
+        assert_eq!(result.err(), Some(String::from("bad item 2")), "First error should be returned");
+        assert_eq!(produced, 3, "Iteration should stop at the error");
+        assert_eq!(drops.get(), 2, "Partially built list should be dropped");
+    }
+
+    #[test]
+    fn test_shrink_to_fit_after_pops() {
+        let mut list = nl();
+        for i in 0..1000 {
+            list.push(i);
+        }
+        for _ in 0..990 {
+            list.pop();
+        }
+        assert_eq!(list.capacity(), 1024, "Popping should not release memory");
+        list.shrink_to_fit();
+        assert_eq!(list.capacity(), 10, "Capacity should shrink to the length");
+        assert_eq!(list.as_slice(), &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9], "Elements should survive the shrink");
+    }
+
+    #[test]
+    fn test_shrink_to_fit_empty_list_frees_buffer() {
+        let mut list: List<String> = nl();
+        list.push(String::from("a"));
         list.pop();
-        assert_eq!(list.get(0), None, "List should be empty after push and pop");
+        list.shrink_to_fit();
+        assert_eq!(list.capacity(), 0, "Empty list should free its buffer");
+        list.push(String::from("b"));
+        assert_eq!(list.get(0), Some(&String::from("b")), "List should still be usable");
     }
 
     #[test]
-    fn test_insert_into_empty_list() {
-        let mut list: List<i32> = nl();
-        list.insert(0, 42);
-        assert_eq!(list.get(0), Some(&42), "Insert into empty list should place element at index 0");
-        assert_eq!(list.len, 1, "Length should be 1 after insert");
-        assert_eq!(list.get(1), None, "Index 1 should be out of bounds");
+    fn test_truncate_runs_exact_destructors() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for _ in 0..5 {
+            list.push(DropCounter(&drops));
+        }
+        list.truncate(5);
+        assert_eq!(drops.get(), 0, "Truncating to the current length should not drop anything");
+        list.truncate(2);
+        assert_eq!(drops.get(), 3, "Exactly the truncated tail should be dropped");
+        list.truncate(4);
+        assert_eq!(drops.get(), 3, "Truncating past the length should not drop anything");
+        drop(list);
+        assert_eq!(drops.get(), 5, "Remaining elements should be dropped with the list");
     }
 
     #[test]
-    fn test_insert_at_start() {
+    fn test_swap_remove_if_predicate_holds() {
+        let mut list = nl();
+        for i in 1..=4 {
+            list.push(i);
+        }
+        assert_eq!(list.swap_remove_if(0, |x| *x == 1), Some(1), "Matching element should be removed");
+        assert_eq!(list.as_slice(), &[4, 2, 3], "Last element should fill the gap");
+        assert_eq!(list.swap_remove_if(2, |_| true), Some(3), "Removing the last element should work");
+        assert_eq!(list.as_slice(), &[4, 2]);
+    }
+
+    #[test]
+    fn test_swap_remove_if_predicate_fails() {
         let mut list = nl();
         list.push(1);
         list.push(2);
-        list.insert(0, 0);
-        assert_eq!(list.get(0), Some(&0), "Insert at start should place element at index 0");
-        assert_eq!(list.get(1), Some(&1), "Original first element should be shifted");
-        assert_eq!(list.get(2), Some(&2), "Original second element should be shifted");
-        assert_eq!(list.len, 3, "Length should be 3 after insert");
+        assert_eq!(list.swap_remove_if(0, |x| *x > 1), None, "Non-matching element should stay");
+        assert_eq!(list.as_slice(), &[1, 2], "List should be unchanged");
     }
 
     #[test]
-    fn test_insert_at_end() {
+    fn test_swap_remove_if_out_of_range() {
         let mut list = nl();
         list.push(1);
-        list.push(2);
-        list.insert(2, 3);
-        assert_eq!(list.get(0), Some(&1), "First element should remain");
-        assert_eq!(list.get(1), Some(&2), "Second element should remain");
-        assert_eq!(list.get(2), Some(&3), "Insert at end should place element at index 2");
-        assert_eq!(list.len, 3, "Length should be 3 after insert");
+        assert_eq!(list.swap_remove_if(1, |_| panic!("pred should not be called")), None, "Out of range index should return None");
+        assert_eq!(list.as_slice(), &[1], "List should be unchanged");
     }
 
     #[test]
-    fn test_insert_in_middle() {
+    fn test_clear_keeps_capacity() {
         let mut list = nl();
         list.push(1);
+        list.push(2);
         list.push(3);
-        list.insert(1, 2);
-        assert_eq!(list.get(0), Some(&1), "First element should remain");
-        assert_eq!(list.get(1), Some(&2), "Inserted element should be at index 1");
-        assert_eq!(list.get(2), Some(&3), "Original second element should be shifted");
-        assert_eq!(list.len, 3, "Length should be 3 after insert");
+        list.clear();
+        assert_eq!(list.len, 0, "Length should be 0 after clear");
+        assert_eq!(list.capacity(), 4, "Capacity should be untouched by clear");
+        list.push(4);
+        assert_eq!(list.as_slice(), &[4], "List should be reusable after clear");
+    }
+
+    #[test]
+    fn test_clear_frees_heap_elements() {
+        let reg = Region::new(GLOBAL);
+        {
+            let mut list: List<String> = nl();
+            list.push(String::from("hello"));
+            list.push(String::from("world"));
+            list.clear();
+            list.push(String::from("again"));
+        }
+        let change = reg.change();
+        assert_eq!(change.allocations, change.deallocations);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn test_as_bytes_round_trip() {
+        let mut list: List<u32> = nl();
+        list.push(1);
+        list.push(0x0102_0304);
+        assert_eq!(list.as_bytes().len(), 8, "Byte view should cover len * size_of::<T>() bytes");
+
+        let bytes = list.as_bytes().to_vec();
+        let restored: &[u32] = bytemuck::cast_slice(list.as_bytes());
+        assert_eq!(restored, &[1, 0x0102_0304], "Byte view should round trip back to the elements");
+
+        list.as_bytes_mut().copy_from_slice(&[0; 8]);
+        assert_eq!(list.as_slice(), &[0, 0], "Writing bytes should update the elements");
+        list.as_bytes_mut().copy_from_slice(&bytes);
+        assert_eq!(list.as_slice(), &[1, 0x0102_0304], "Restored bytes should give back the elements");
+    }
+
+    #[test]
+    fn test_swap_remove_middle() {
+        let mut list = nl();
+        for i in 1..=4 {
+            list.push(i);
+        }
+        assert_eq!(list.swap_remove(1), 2, "swap_remove should return the removed element");
+        assert_eq!(list.as_slice(), &[1, 4, 3], "Last element should move into the gap");
+    }
+
+    #[test]
+    fn test_swap_remove_last() {
+        let mut list: List<String> = nl();
+        list.push(String::from("a"));
+        list.push(String::from("b"));
+        assert_eq!(list.swap_remove(1), String::from("b"), "Removing the last element should return it");
+        assert_eq!(list.as_slice(), &[String::from("a")]);
+        assert_eq!(list.swap_remove(0), String::from("a"));
+        assert_eq!(list.len, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_swap_remove_out_of_bounds() {
+        let mut list = nl();
+        list.push(1);
+        list.swap_remove(1);
+    }
+
+    #[test]
+    fn test_retain_keeps_evens() {
+        let mut list = nl();
+        for i in 0..10 {
+            list.push(i);
+        }
+        list.retain(|x| x % 2 == 0);
+        assert_eq!(list.as_slice(), &[0, 2, 4, 6, 8], "Even elements should be kept in order");
+    }
+
+    #[test]
+    fn test_retain_drops_rejected_elements() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for i in 0..6 {
+            list.push((i, DropCounter(&drops)));
+        }
+        list.retain(|e| e.0 < 2);
+        assert_eq!(drops.get(), 4, "Rejected elements should be dropped");
+        assert_eq!(list.len, 2, "Accepted elements should remain");
+        drop(list);
+        assert_eq!(drops.get(), 6, "Every element should be dropped exactly once");
+    }
+
+    #[test]
+    fn test_retain_panicking_predicate_keeps_list_consistent() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for i in 0..6 {
+            list.push((i, DropCounter(&drops)));
+        }
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.retain(|e| {
+                if e.0 == 3 {
+                    panic!("predicate failed");
+                }
+                e.0 % 2 == 0
+            });
+        }));
+        assert!(result.is_err(), "Predicate panic should propagate");
+        assert_eq!(drops.get(), 1, "Only the element rejected before the panic should be dropped");
+        let order: Vec<i32> = list.iter().map(|e| e.0).collect();
+        assert_eq!(order, vec![0, 2, 3, 4, 5], "Unvisited elements should be kept after the processed ones");
+        drop(list);
+        assert_eq!(drops.get(), 6, "Every element should be dropped exactly once");
+    }
+
+    #[test]
+    fn test_apply_doubles_every_element() {
+        let mut list = nl();
+        for i in 1..=4 {
+            list.push(i);
+        }
+        list.apply(|x| *x *= 2);
+        assert_eq!(list.as_slice(), &[2, 4, 6, 8], "Every element should be doubled");
+        let mut empty: List<i32> = nl();
+        empty.apply(|_| panic!("f should not be called on an empty list"));
+    }
+
+    #[test]
+    fn test_dedup_collapses_runs() {
+        let mut list = nl();
+        for x in [1, 1, 2, 3, 3, 3, 1] {
+            list.push(x);
+        }
+        list.dedup();
+        assert_eq!(list.as_slice(), &[1, 2, 3, 1], "Runs should collapse to their first element");
+    }
+
+    #[test]
+    fn test_dedup_by_key_drops_removed_once() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for x in [1, 1, 2, 3, 3, 3, 1] {
+            list.push((x, DropCounter(&drops)));
+        }
+        list.dedup_by_key(|e| e.0);
+        assert_eq!(drops.get(), 3, "Each removed duplicate should be dropped once");
+        let keys: Vec<i32> = list.iter().map(|e| e.0).collect();
+        assert_eq!(keys, vec![1, 2, 3, 1], "First element of each run should survive");
+        drop(list);
+        assert_eq!(drops.get(), 7, "Every element should be dropped exactly once");
+    }
+
+    #[test]
+    fn test_extend_from_range() {
+        let mut list = nl();
+        list.push(-1);
+        list.extend(0..1000);
+        assert_eq!(list.len, 1001, "Every item should be appended");
+        assert_eq!(list[0], -1, "Existing elements should stay in front");
+        assert!(list.iter().skip(1).copied().eq(0..1000), "Items should be appended in order");
+        assert!(list.capacity() >= 1001 && list.capacity() <= 2048, "Capacity should grow sensibly");
+    }
+
+    #[test]
+    fn test_extend_from_vec_and_refs() {
+        let mut list = nl();
+        list.extend(vec![1, 2]);
+        let more = [3, 4];
+        list.extend(more.iter());
+        list.extend(&vec![5]);
+        assert_eq!(list.as_slice(), &[1, 2, 3, 4, 5], "Owned and borrowed items should be appended in order");
+    }
+
+    #[test]
+    fn test_retain_first_n_evens() {
+        let mut list = nl();
+        list.extend(0..20);
+        list.retain_first_n(3, |x| x % 2 == 0);
+        assert_eq!(list.len, 3, "Only the first 3 matches should be kept");
+        assert_eq!(list.as_slice(), &[0, 2, 4], "Matches should keep their order");
+    }
+
+    #[test]
+    fn test_retain_first_n_fewer_matches() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for i in 0..5 {
+            list.push((i, DropCounter(&drops)));
+        }
+        list.retain_first_n(10, |e| e.0 > 2);
+        assert_eq!(list.len, 2, "All matches should be kept when there are fewer than n");
+        assert_eq!(drops.get(), 3, "Non-matching elements should be dropped");
+        list.retain_first_n(0, |_| true);
+        assert_eq!(list.len, 0, "n = 0 should empty the list");
+        assert_eq!(drops.get(), 5);
+    }
+
+    #[test]
+    fn test_collect_from_map() {
+        let list: List<i32> = (0..5).map(|x| x * x).collect();
+        assert_eq!(list.as_slice(), &[0, 1, 4, 9, 16], "Collected list should hold the mapped values");
+        assert_eq!(list.capacity(), 5, "Exact size iterators should allocate once");
+        let words: List<String> = list.iter().map(|x| x.to_string()).collect();
+        assert_eq!(words[4], "16", "Collecting from a borrowed iterator should work");
+    }
+
+    #[test]
+    fn test_clone_is_deep() {
+        let mut list: List<String> = nl();
+        list.push(String::from("a"));
+        list.push(String::from("b"));
+        let mut copy = list.clone();
+        copy[0].push('!');
+        copy.push(String::from("c"));
+        assert_eq!(list.as_slice(), &[String::from("a"), String::from("b")], "Original should be untouched");
+        assert_eq!(copy.as_slice(), &[String::from("a!"), String::from("b"), String::from("c")]);
+        assert_eq!(list.clone().capacity(), 2, "Clone should allocate exactly len elements");
+    }
+
+    #[test]
+    fn test_clone_from_reuses_allocation() {
+        let mut source: List<String> = nl();
+        source.push(String::from("x"));
+        source.push(String::from("y"));
+        let mut dest: List<String> = List::with_capacity(8);
+        dest.push(String::from("old"));
+        let ptr = dest.as_ptr();
+        dest.clone_from(&source);
+        assert_eq!(dest.as_slice(), source.as_slice(), "Destination should hold clones of the source");
+        assert_eq!(dest.as_ptr(), ptr, "Existing allocation should be reused");
+        assert_eq!(dest.capacity(), 8);
+    }
+
+    #[test]
+    fn test_clone_panic_drops_only_cloned_elements() {
+        struct PanicOnClone<'a>(usize, DropCounter<'a>);
+
+        impl <'a> Clone for PanicOnClone<'a> {
+            fn clone(&self) -> Self {
+                if self.0 == 2 {
+                    panic!("clone failed");
+                }
+                PanicOnClone(self.0, DropCounter(self.1.0))
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut list = nl();
+        for i in 0..4 {
+            list.push(PanicOnClone(i, DropCounter(&drops)));
+        }
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| list.clone()));
+        assert!(result.is_err(), "Clone panic should propagate");
+        assert_eq!(drops.get(), 2, "Only the two finished clones should be dropped");
+        drop(list);
+        assert_eq!(drops.get(), 6);
+    }
+
+    #[test]
+    fn test_extend_dedup_skips_existing_values() {
+        let mut list: List<i32> = (1..=3).collect();
+        list.extend_dedup(vec![2, 3, 4, 4, 5]);
+        assert_eq!(list.as_slice(), &[1, 2, 3, 4, 5], "Only new values should be appended, in first-seen order");
+    }
+
+    #[test]
+    fn test_extend_dedup_drops_skipped_items() {
+        struct Record<'a>(u32, DropCounter<'a>);
+
+        impl <'a> PartialEq for Record<'a> {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl <'a> Eq for Record<'a> {}
+
+        impl <'a> Hash for Record<'a> {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.0.hash(state);
+            }
+        }
+
+        let drops = Cell::new(0);
+        let mut list = nl();
+        list.push(Record(1, DropCounter(&drops)));
+        list.extend_dedup([1, 2, 2, 3].into_iter().map(|id| Record(id, DropCounter(&drops))));
+        assert_eq!(drops.get(), 2, "Skipped records should be dropped");
+        let ids: Vec<u32> = list.iter().map(|r| r.0).collect();
+        assert_eq!(ids, vec![1, 2, 3], "Only new ids should be appended");
+        drop(list);
+        assert_eq!(drops.get(), 5, "Every record should be dropped exactly once");
+    }
+
+    #[test]
+    fn test_debug_matches_vec() {
+        let list: List<i32> = (1..=3).collect();
+        assert_eq!(format!("{:?}", list), "[1, 2, 3]", "Debug should print like a slice");
+        let nested: List<List<i32>> = (0..2).map(|i| (0..i).collect()).collect();
+        assert_eq!(format!("{:?}", nested), format!("{:?}", vec![vec![], vec![0]]), "Nested lists should match Vec");
+        assert_eq!(format!("{:#?}", list), format!("{:#?}", vec![1, 2, 3]), "Pretty printing should match Vec");
+    }
+
+    #[test]
+    fn test_equal_lists() {
+        let a: List<i32> = (1..=3).collect();
+        let b: List<i32> = (1..=3).collect();
+        assert_eq!(a, b, "Lists with the same elements should be equal");
+        let empty: List<i32> = nl();
+        assert_eq!(empty, List::<i32>::new(), "Empty lists should be equal");
+    }
+
+    #[test]
+    fn test_lists_of_different_length_differ() {
+        let a: List<i32> = (1..=3).collect();
+        let b: List<i32> = (1..=4).collect();
+        assert_ne!(a, b, "Lists of different lengths should not be equal");
+        let c: List<i32> = [1, 2, 4].into_iter().collect();
+        assert_ne!(a, c, "Lists with a differing element should not be equal");
+    }
+
+    #[test]
+    fn test_list_compares_with_slices_vecs_and_arrays() {
+        let list: List<i32> = (1..=3).collect();
+        assert!(list == [1, 2, 3], "List should compare with an array");
+        assert!(list != [1, 2], "Arrays of different length should not be equal");
+        assert_eq!(list, vec![1, 2, 3], "List should compare with a Vec");
+        assert_eq!(list, &[1, 2, 3][..], "List should compare with a slice");
+        assert_eq!(list, *[1, 2, 3].as_slice(), "List should compare with an unsized slice");
+    }
+
+    #[test]
+    fn test_lists_in_btree_set_are_sorted() {
+        let mut set = std::collections::BTreeSet::new();
+        set.insert(List::from_iter([2, 1]));
+        set.insert(List::from_iter([1, 2, 3]));
+        set.insert(List::from_iter([1, 2]));
+        set.insert(List::new());
+        set.insert(List::from_iter([1, 2]));
+        let sorted: Vec<Vec<i32>> = set.into_iter().map(|l| l.into_iter().collect()).collect();
+        assert_eq!(sorted, vec![vec![], vec![1, 2], vec![1, 2, 3], vec![2, 1]], "Lists should be ordered lexicographically");
+    }
+
+    #[test]
+    fn test_equal_lists_hash_equal() {
+        let mut set = HashSet::new();
+        assert!(set.insert(List::from_iter(["a", "b"])));
+        assert!(!set.insert(List::from_iter(["a", "b"])), "Equal lists should collide in a HashSet");
+        assert!(set.insert(List::from_iter(["ab"])), "Different lists should be distinct");
+        assert!(List::from_iter([1.0]) < List::from_iter([1.0, f64::NAN]), "Shorter prefix should sort first");
+    }
+
+    #[test]
+    fn test_default_list_is_empty() {
+        let list: List<i32> = List::default();
+        assert!(list.is_empty(), "Default list should be empty");
+        assert_eq!(list.capacity(), 0, "Default list should not allocate");
+    }
+
+    #[test]
+    fn test_list_macro() {
+        let repeated = list![5; 3];
+        assert_eq!(repeated, [5, 5, 5], "list![elem; n] should repeat elem n times");
+        assert_eq!(repeated.capacity(), 3, "list![elem; n] should allocate exactly n slots");
+
+        let strings = list![String::from("a"); 2];
+        assert_eq!(strings, ["a", "a"], "list![elem; n] should clone non Copy values");
+
+        let listed = list![1, 2, 3,];
+        assert_eq!(listed, [1, 2, 3], "list![a, b, c] should keep the given order");
+
+        let empty: List<i32> = list![];
+        assert!(empty.is_empty(), "list![] should be empty");
+        assert!(list![7; 0].is_empty(), "list![elem; 0] should be empty");
+    }
+
+    #[test]
+    fn test_iter_borrows_in_both_directions() {
+        let list: List<i32> = (1..=4).collect();
+        let mut iter = list.iter();
+        assert_eq!(iter.size_hint(), (4, Some(4)), "Fresh iterator should report every element");
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.size_hint(), (2, Some(2)), "size_hint should shrink from both ends");
+        assert_eq!(iter.collect::<Vec<_>>(), vec![&2, &3]);
+
+        let mut seen = Vec::new();
+        for x in &list {
+            seen.push(*x);
+        }
+        assert_eq!(seen, vec![1, 2, 3, 4], "for over &List should visit every element in order");
+        assert_eq!(list.iter().rev().copied().collect::<Vec<_>>(), vec![4, 3, 2, 1]);
+        assert_eq!(nl::<i32>().iter().next(), None, "Empty list should yield nothing");
+    }
+
+    #[test]
+    fn test_iter_mut_mutates_in_place() {
+        let mut list: List<String> = ["a", "b", "c"].into_iter().map(String::from).collect();
+        for s in &mut list {
+            s.push('!');
+        }
+        if let Some(last) = list.iter_mut().next_back() {
+            last.push('?');
+        }
+        assert_eq!(list, ["a!", "b!", "c!?"], "iter_mut should write through to the list");
+        assert_eq!(list.iter_mut().size_hint(), (3, Some(3)));
+    }
+
+    #[test]
+    fn test_iterators_cross_threads() {
+        let mut list = list![1, 2, 3];
+        std::thread::scope(|s| {
+            let iter = list.iter();
+            let sum = s.spawn(move || iter.sum::<i32>()).join().unwrap();
+            assert_eq!(sum, 6, "Iter should be usable from another thread");
+        });
+        std::thread::scope(|s| {
+            let iter = list.iter_mut();
+            s.spawn(move || iter.for_each(|x| *x *= 10));
+        });
+        assert_eq!(list, [10, 20, 30], "IterMut should be usable from another thread");
+    }
+
+    #[test]
+    fn test_resize_grows_and_shrinks() {
+        let mut list = list![1, 2];
+        list.resize(5, 9);
+        assert_eq!(list, [1, 2, 9, 9, 9], "resize should fill new slots with the value");
+        list.resize(1, 0);
+        assert_eq!(list, [1], "resize should drop the tail when shrinking");
+        list.resize(1, 0);
+        assert_eq!(list, [1], "resize to the current length should do nothing");
+    }
+
+    #[test]
+    fn test_resize_moves_the_last_value() {
+        struct CloneCounter<'a>(&'a Cell<usize>);
+        impl Clone for CloneCounter<'_> {
+            fn clone(&self) -> Self {
+                self.0.set(self.0.get() + 1);
+                CloneCounter(self.0)
+            }
+        }
+
+        let clones = Cell::new(0);
+        let mut list = nl();
+        list.resize(4, CloneCounter(&clones));
+        assert_eq!(list.len(), 4);
+        assert_eq!(clones.get(), 3, "resize should clone one less time than it adds");
+    }
+
+    #[test]
+    fn test_resize_with_calls_closure_per_slot() {
+        let drops = Cell::new(0);
+        let mut next = 0;
+        let mut list = nl();
+        list.resize_with(3, || { next += 1; next });
+        assert_eq!(list, [1, 2, 3], "resize_with should call f for every new slot in order");
+
+        let mut counters = nl();
+        counters.resize_with(5, || DropCounter(&drops));
+        counters.resize_with(2, || unreachable!());
+        assert_eq!(drops.get(), 3, "Shrinking should drop exactly the removed elements");
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut list: List<i32> = (1..=5).collect();
+        let tail = list.split_off(2);
+        assert_eq!(list, [1, 2], "split_off should keep the head in self");
+        assert_eq!(tail, [3, 4, 5], "split_off should return the tail in order");
+        assert_eq!(tail.capacity(), 3, "The tail should be sized to fit");
+
+        let rest = list.split_off(2);
+        assert!(rest.is_empty(), "Splitting at len should return an empty list");
+        let all = list.split_off(0);
+        assert!(list.is_empty());
+        assert_eq!(all, [1, 2], "Splitting at 0 should move everything");
+    }
+
+    #[test]
+    fn test_split_off_drops_nothing() {
+        let drops = Cell::new(0);
+        let mut list = nl();
+        list.resize_with(4, || DropCounter(&drops));
+        let tail = list.split_off(1);
+        assert_eq!(drops.get(), 0, "Moving the tail should not run destructors");
+        drop(tail);
+        assert_eq!(drops.get(), 3);
+        drop(list);
+        assert_eq!(drops.get(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_split_off_past_len_panics() {
+        let mut list = list![1, 2];
+        list.split_off(3);
     }
 
     #[test]
-    fn test_insert_with_strings() {
-        let mut list: List<String> = nl();
-        list.push(String::from("a"));
-        list.push(String::from("c"));
-        list.insert(1, String::from("b"));
-        assert_eq!(list.get(0), Some(&String::from("a")), "First element should remain");
-        assert_eq!(list.get(1), Some(&String::from("b")), "Inserted element should be at index 1");
-        assert_eq!(list.get(2), Some(&String::from("c")), "Original second element should be shifted");
-        assert_eq!(list.len, 3, "Length should be 3 after insert");
+    fn test_append_moves_all_elements() {
+        let mut list = list![1, 2];
+        let mut other = list![3, 4];
+        let other_cap = other.capacity();
+        list.append(&mut other);
+        assert_eq!(list, [1, 2, 3, 4], "append should add other's elements in order");
+        assert_eq!(other.len(), 0, "append should leave other empty");
+        assert_eq!(other.capacity(), other_cap, "append should keep other's allocation");
+
+        other.push(5);
+        list.append(&mut other);
+        list.append(&mut nl());
+        assert_eq!(list, [1, 2, 3, 4, 5], "Appending an empty list should do nothing");
     }
 
     #[test]
-    fn test_insert_triggers_growth() {
+    fn test_append_drops_nothing() {
+        let drops = Cell::new(0);
         let mut list = nl();
-        // Fill to capacity (assuming initial capacity is 0, grows to 1, then 2, etc.)
-        list.push(1); // cap = 1
-        list.push(2); // cap = 2
-        list.push(3); // cap = 4
-        list.push(4);
-        let initial_cap = list.cap();
-        list.insert(1, 5); // Should trigger growth if cap is full
-        assert_eq!(list.get(0), Some(&1), "First element should remain");
-        assert_eq!(list.get(1), Some(&5), "Inserted element should be at index 1");
-        assert_eq!(list.get(2), Some(&2), "Second element should be shifted");
-        assert_eq!(list.get(3), Some(&3), "Third element should be shifted");
-        assert_eq!(list.get(4), Some(&4), "Third element should be shifted");
-        assert_eq!(list.len, 5, "Length should be 4 after insert");
-        assert!(list.cap() > initial_cap, "Capacity should increase after insert");
+        let mut other = nl();
+        other.resize_with(3, || DropCounter(&drops));
+        list.append(&mut other);
+        drop(other);
+        assert_eq!(drops.get(), 0, "Moved elements should not be dropped with other");
+        drop(list);
+        assert_eq!(drops.get(), 3, "Moved elements should be dropped exactly once");
     }
 
     #[test]
-    #[should_panic(expected = "index out of bounds")]
-    fn test_insert_out_of_bounds() {
-        let mut list = nl();
-        list.push(1);
-        list.insert(2, 2); // Should panic since index 2 > len
+    fn test_drain_middle_range() {
+        let mut list: List<i32> = (0..8).collect();
+        let drained: Vec<i32> = list.drain(2..5).collect();
+        assert_eq!(drained, vec![2, 3, 4], "drain should yield exactly the range");
+        assert_eq!(list, [0, 1, 5, 6, 7], "The tail should be shifted down over the gap");
+
+        list.drain(3..=3);
+        assert_eq!(list, [0, 1, 5, 7], "Inclusive ranges should be supported");
+        list.drain(..1);
+        list.drain(2..);
+        assert_eq!(list, [1, 5], "Open ranges should drain to the ends");
+        list.drain(1..1);
+        assert_eq!(list, [1, 5], "Empty ranges should leave the list alone");
     }
 
     #[test]
-    fn test_multiple_inserts() {
+    fn test_drain_partially_consumed_range() {
+        let drops = Cell::new(0);
         let mut list = nl();
-        list.push(2);
-        list.push(4);
-        list.insert(0, 1);
-        list.insert(2, 3);
-        list.insert(4, 5);
-        assert_eq!(list.get(0), Some(&1), "First insert should be at index 0");
-        assert_eq!(list.get(1), Some(&2), "Original first element shifted");
-        assert_eq!(list.get(2), Some(&3), "Second insert should be at index 2");
-        assert_eq!(list.get(3), Some(&4), "Original second element shifted");
-        assert_eq!(list.get(4), Some(&5), "Third insert should be at index 4");
-        assert_eq!(list.len, 5, "Length should be 5 after inserts");
+        list.resize_with(6, || DropCounter(&drops));
+        {
+            let mut iter = list.drain(1..4);
+            assert_eq!(iter.size_hint(), (3, Some(3)));
+            drop(iter.next_back());
+            assert_eq!(drops.get(), 1);
+        }
+        assert_eq!(drops.get(), 3, "Unyielded elements should be dropped with the Drain");
+        assert_eq!(list.len(), 3, "Only the drained range should be removed");
+
+        let mut strings: List<String> = ["a", "b", "c", "d"].into_iter().map(String::from).collect();
+        assert_eq!(strings.drain(1..3).next(), Some(String::from("b")));
+        assert_eq!(strings, ["a", "d"], "Tail should close the gap after a partial drain");
     }
 
     #[test]
-    fn test_insert_with_deref() {
+    fn test_forgotten_drain_leaks_instead_of_double_dropping() {
+        let drops = Cell::new(0);
         let mut list = nl();
-        list.push(1);
-        list.push(3);
-        list.insert(1, 2);
-        let slice: &[i32] = &list;
-        assert_eq!(slice, &[1, 2, 3], "Deref should return correct slice after insert");
+        list.resize_with(5, || DropCounter(&drops));
+        mem::forget(list.drain(1..3));
+        assert_eq!(list.len(), 1, "A forgotten Drain should leave only the head");
+        drop(list);
+        assert_eq!(drops.get(), 1, "Leaked elements should never be dropped");
     }
 
     #[test]
-    fn test_remove_from_single_element_list() {
-        let mut list = nl();
-        list.push(42);
-        let removed = list.remove(0);
-        assert_eq!(removed, 42, "Remove should return the removed element");
-        assert_eq!(list.len, 0, "Length should be 0 after remove");
-        assert_eq!(list.get(0), None, "List should be empty after remove");
+    #[should_panic(expected = "range end out of bounds")]
+    fn test_drain_range_past_len_panics() {
+        let mut list = list![1, 2, 3];
+        list.drain(1..4);
     }
 
     #[test]
-    fn test_remove_from_start() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        let removed = list.remove(0);
-        assert_eq!(removed, 1, "Remove should return first element");
-        assert_eq!(list.get(0), Some(&2), "Second element should shift to index 0");
-        assert_eq!(list.get(1), Some(&3), "Third element should shift to index 1");
-        assert_eq!(list.len, 2, "Length should be 2 after remove");
+    fn test_splice_longer_replacement() {
+        let mut list = list![0, 1, 2, 3];
+        let removed: Vec<i32> = list.splice(1..3, [9, 9, 9]).collect();
+        assert_eq!(removed, vec![1, 2], "splice should yield the removed range");
+        assert_eq!(list, [0, 9, 9, 9, 3], "Replacement should fill the gap and push the tail back");
     }
 
     #[test]
-    fn test_remove_from_end() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        let removed = list.remove(2);
-        assert_eq!(removed, 3, "Remove should return last element");
-        assert_eq!(list.get(0), Some(&1), "First element should remain");
-        assert_eq!(list.get(1), Some(&2), "Second element should remain");
-        assert_eq!(list.get(2), None, "Index 2 should be out of bounds");
-        assert_eq!(list.len, 2, "Length should be 2 after remove");
+    fn test_splice_shorter_and_empty_replacement() {
+        let mut list: List<i32> = (0..6).collect();
+        list.splice(1..5, [7]);
+        assert_eq!(list, [0, 7, 5], "Shorter replacement should compact the tail");
+        list.splice(..1, []);
+        assert_eq!(list, [7, 5], "Empty replacement should behave like drain");
+        list.splice(2.., [8, 9]);
+        assert_eq!(list, [7, 5, 8, 9], "Splicing an empty range at the end should append");
+        list.splice(1..1, [1, 2]);
+        assert_eq!(list, [7, 1, 2, 5, 8, 9], "Splicing an empty range should insert");
     }
 
     #[test]
-    fn test_remove_from_middle() {
+    fn test_splice_drops_removed_elements_once() {
+        let drops = Cell::new(0);
         let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        let removed = list.remove(1);
-        assert_eq!(removed, 2, "Remove should return middle element");
-        assert_eq!(list.get(0), Some(&1), "First element should remain");
-        assert_eq!(list.get(1), Some(&3), "Third element should shift to index 1");
-        assert_eq!(list.get(2), None, "Index 2 should be out of bounds");
-        assert_eq!(list.len, 2, "Length should be 2 after remove");
+        list.resize_with(4, || DropCounter(&drops));
+        {
+            let mut splice = list.splice(0..2, [DropCounter(&drops)]);
+            drop(splice.next());
+            assert_eq!(drops.get(), 1);
+        }
+        assert_eq!(drops.get(), 2, "Unyielded removed elements should be dropped with the Splice");
+        assert_eq!(list.len(), 3);
+        drop(list);
+        assert_eq!(drops.get(), 5, "Every element should be dropped exactly once");
     }
 
     #[test]
-    fn test_remove_with_strings() {
-        let mut list: List<String> = nl();
-        list.push(String::from("a"));
-        list.push(String::from("b"));
-        list.push(String::from("c"));
-        let removed = list.remove(1);
-        assert_eq!(removed, String::from("b"), "Remove should return middle string");
-        assert_eq!(list.get(0), Some(&String::from("a")), "First string should remain");
-        assert_eq!(list.get(1), Some(&String::from("c")), "Third string should shift to index 1");
-        assert_eq!(list.len, 2, "Length should be 2 after remove");
+    fn test_extend_from_slice() {
+        let mut bytes: List<u8> = list![1];
+        bytes.extend_from_slice(&[2, 3]);
+        bytes.extend_from_slice_copy(b"ab");
+        bytes.extend_from_slice_copy(&[]);
+        assert_eq!(bytes, [1, 2, 3, b'a', b'b'], "Both variants should append in order");
+
+        let source = [String::from("x"), String::from("y")];
+        let mut strings = list![String::from("w")];
+        strings.extend_from_slice(&source);
+        assert_eq!(strings, ["w", "x", "y"], "extend_from_slice should clone non Copy values");
+        assert_eq!(source, ["x", "y"], "The source slice should be untouched");
     }
 
     #[test]
-    #[should_panic(expected = "index out of bounds")]
-    fn test_remove_out_of_bounds() {
+    fn test_extend_from_slice_keeps_clones_made_before_a_panic() {
+        struct PanicOnClone(bool);
+        impl Clone for PanicOnClone {
+            fn clone(&self) -> Self {
+                assert!(!self.0, "clone failed");
+                PanicOnClone(self.0)
+            }
+        }
+
+        let source = [PanicOnClone(false), PanicOnClone(false), PanicOnClone(true), PanicOnClone(false)];
         let mut list = nl();
-        list.push(1);
-        list.remove(1); // Should panic since index 1 >= len
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| list.extend_from_slice(&source)));
+        assert!(result.is_err());
+        assert_eq!(list.len(), 2, "Only the successfully cloned elements should be in the list");
     }
 
     #[test]
-    #[should_panic(expected = "index out of bounds")]
-    fn test_remove_from_empty_list() {
-        let mut list: List<i32> = nl();
-        list.remove(0); // Should panic since list is empty
+    fn test_vec_round_trip_reuses_the_buffer() {
+        let vec: Vec<String> = ["a", "b", "c"].into_iter().map(String::from).collect();
+        let vec_ptr = vec.as_ptr();
+        let vec_cap = vec.capacity();
+
+        let mut list = List::from(vec);
+        assert_eq!(list, ["a", "b", "c"], "Converting from a Vec should keep the contents");
+        assert_eq!(list.as_ptr(), vec_ptr, "Converting from a Vec should not copy");
+        assert_eq!(list.capacity(), vec_cap);
+
+        list.push(String::from("d"));
+        let back: Vec<String> = list.into();
+        assert_eq!(back, ["a", "b", "c", "d"], "Converting back should keep the contents");
+
+        let empty: Vec<i32> = List::from(Vec::new()).into();
+        assert!(empty.is_empty());
     }
 
     #[test]
-    fn test_multiple_removes() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        list.push(4);
-        let removed1 = list.remove(0);
-        let removed2 = list.remove(1);
-        assert_eq!(removed1, 1, "First remove should return 1");
-        assert_eq!(removed2, 3, "Second remove should return 3");
-        assert_eq!(list.get(0), Some(&2), "Second element should be at index 0");
-        assert_eq!(list.get(1), Some(&4), "Fourth element should be at index 1");
-        assert_eq!(list.len, 2, "Length should be 2 after removes");
+    fn test_vec_round_trip_drops_elements_once() {
+        let drops = Cell::new(0);
+        let vec: Vec<DropCounter> = (0..3).map(|_| DropCounter(&drops)).collect();
+        let list = List::from(vec);
+        let vec = Vec::from(list);
+        assert_eq!(drops.get(), 0, "Converting should not drop anything");
+        drop(vec);
+        assert_eq!(drops.get(), 3, "Every element should be dropped exactly once");
     }
 
     #[test]
-    fn test_remove_with_deref() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        list.remove(1);
-        let slice: &[i32] = &list;
-        assert_eq!(slice, &[1, 3], "Deref should return correct slice after remove");
+    fn test_list_as_ref_and_borrow() {
+        fn sum(s: impl AsRef<[i32]>) -> i32 {
+            s.as_ref().iter().sum()
+        }
+        fn zero_first(mut s: impl AsMut<[i32]>) {
+            s.as_mut()[0] = 0;
+        }
+
+        let mut list = list![1, 2, 3];
+        assert_eq!(sum(&list), 6, "A borrowed List should work as AsRef<[T]>");
+        zero_first(&mut list);
+        assert_eq!(sum(list), 5, "AsMut should write through to the list");
+
+        let mut map = HashMap::new();
+        map.insert(list![1, 2], "found");
+        assert_eq!(map.get(&[1, 2][..]), Some(&"found"), "Lists keyed in a map should be found by slice");
     }
 
     #[test]
-    fn test_remove_preserves_capacity() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        let initial_cap = list.cap();
+    fn test_zst_push_pop_and_iterate() {
+        let mut list: List<()> = nl();
+        assert_eq!(list.capacity(), usize::MAX, "Zero sized types should report unlimited capacity");
+        for _ in 0..5000 {
+            list.push(());
+        }
+        assert_eq!(list.len(), 5000);
+        assert_eq!(list.allocated_bytes(), 0, "Zero sized types should never allocate");
+        assert_eq!(list.get(4999), Some(&()));
+        assert_eq!(list.get(5000), None);
+
+        let mut iter = list.iter();
+        assert_eq!(iter.size_hint(), (5000, Some(5000)));
+        iter.next();
+        iter.next_back();
+        assert_eq!(iter.size_hint(), (4998, Some(4998)), "size_hint should count ZSTs from both ends");
+        assert_eq!(iter.count(), 4998);
+
+        list.insert(10, ());
         list.remove(0);
-        assert_eq!(list.cap(), initial_cap, "Capacity should not change after remove");
-        assert_eq!(list.len, 1, "Length should be 1 after remove");
-        assert_eq!(list.get(0), Some(&2), "Remaining element should be correct");
+        for _ in 0..3000 {
+            assert_eq!(list.pop(), Some(()));
+        }
+        assert_eq!(list.len(), 2000);
+
+        let mut into_iter = list.into_iter();
+        assert_eq!(into_iter.size_hint(), (2000, Some(2000)));
+        into_iter.next_back();
+        assert_eq!(into_iter.count(), 1999);
     }
 
     #[test]
-    fn test_into_iter_empty_list() {
-        let list: List<i32> = nl();
-        let mut iter = list.into_iter();
-        assert_eq!(iter.next(), None, "Empty iterator should yield None");
-        assert_eq!(iter.next_back(), None, "Empty iterator should yield None for next_back");
-        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) for empty iterator");
+    fn test_zst_drops_every_element() {
+        struct Zst;
+        thread_local!(static DROPS: Cell<usize> = const { Cell::new(0) });
+        impl Drop for Zst {
+            fn drop(&mut self) {
+                DROPS.with(|d| d.set(d.get() + 1));
+            }
+        }
+
+        let mut list = List::with_capacity(8);
+        for _ in 0..100 {
+            list.push(Zst);
+        }
+        list.truncate(60);
+        assert_eq!(DROPS.with(Cell::get), 40, "truncate should drop the removed ZSTs");
+        list.drain(10..20);
+        assert_eq!(DROPS.with(Cell::get), 50, "drain should drop the drained ZSTs");
+        drop(list);
+        assert_eq!(DROPS.with(Cell::get), 100, "Dropping the list should drop the rest");
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_into_iter_single_element() {
-        let mut list = nl();
-        list.push(42);
-        let mut iter = list.into_iter();
-        assert_eq!(iter.size_hint(), (1, Some(1)), "Size hint should be (1, Some(1))");
-        assert_eq!(iter.next(), Some(42), "Iterator should yield single element");
-        assert_eq!(iter.next(), None, "Iterator should be exhausted after one element");
-        assert_eq!(iter.next_back(), None, "Exhausted iterator should yield None for next_back");
-        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after exhaustion");
+    fn test_serde_json_round_trip() {
+        let numbers = list![1, -2, 3];
+        let json = serde_json::to_string(&numbers).unwrap();
+        assert_eq!(json, "[1,-2,3]", "List should serialize like a Vec");
+        let back: List<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, numbers, "Round trip should preserve the contents");
+
+        let strings: List<String> = ["a", "b"].into_iter().map(String::from).collect();
+        let json = serde_json::to_string(&strings).unwrap();
+        assert_eq!(json, r#"["a","b"]"#);
+        let back: List<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, strings);
+
+        assert!(serde_json::from_str::<List<i32>>("{}").is_err(), "Non sequences should be rejected");
     }
 
     #[test]
-    fn test_into_iter_multiple_elements_forward() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        let mut iter = list.into_iter();
-        assert_eq!(iter.size_hint(), (3, Some(3)), "Size hint should be (3, Some(3))");
-        assert_eq!(iter.next(), Some(1), "First element should be 1");
-        assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should update after next");
-        assert_eq!(iter.next(), Some(2), "Second element should be 2");
-        assert_eq!(iter.next(), Some(3), "Third element should be 3");
-        assert_eq!(iter.next(), None, "Iterator should be exhausted");
-        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after exhaustion");
+    fn test_byte_list_is_io_write() {
+        use std::io::Write;
+
+        let mut out: List<u8> = nl();
+        write!(out, "id-{:02}", 7).unwrap();
+        out.write_all(b"!").unwrap();
+        out.flush().unwrap();
+        assert_eq!(out, *b"id-07!", "Formatted output should be appended as bytes");
+
+        fn write_to(mut w: impl Write) {
+            w.write_all(b"xyz").unwrap();
+        }
+        write_to(&mut out);
+        assert_eq!(&out[6..], b"xyz", "&mut List<u8> should work where a Write is expected");
     }
 
     #[test]
-    fn test_into_iter_multiple_elements_backward() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        let mut iter = list.into_iter();
-        assert_eq!(iter.next_back(), Some(3), "Last element should be 3");
-        assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should update after next_back");
-        assert_eq!(iter.next_back(), Some(2), "Second-to-last element should be 2");
-        assert_eq!(iter.next_back(), Some(1), "First element should be 1");
-        assert_eq!(iter.next_back(), None, "Iterator should be exhausted");
-        assert_eq!(iter.next(), None, "Exhausted iterator should yield None for next");
+    fn test_byte_list_is_fmt_write() {
+        use std::fmt::Write;
+
+        let mut out: List<u8> = nl();
+        write!(out, "{}+{}=", 1, 2).unwrap();
+        out.write_char('ß').unwrap();
+        out.write_char('🦀').unwrap();
+        assert_eq!(out.len(), 4 + 2 + 4, "Multi byte chars should be encoded in full");
+        assert_eq!(std::str::from_utf8(&out), Ok("1+2=ß🦀"), "Output should be valid UTF-8");
     }
 
     #[test]
-    fn test_into_iter_mixed_forward_backward() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        let mut iter = list.into_iter();
-        assert_eq!(iter.next(), Some(1), "First element should be 1");
-        assert_eq!(iter.next_back(), Some(3), "Last element should be 3");
-        assert_eq!(iter.size_hint(), (1, Some(1)), "Size hint should be (1, Some(1))");
-        assert_eq!(iter.next(), Some(2), "Middle element should be 2");
-        assert_eq!(iter.next(), None, "Iterator should be exhausted");
-        assert_eq!(iter.next_back(), None, "Exhausted iterator should yield None");
+    fn test_display_list() {
+        assert_eq!(list![1, 2, 3].to_string(), "[1, 2, 3]", "Display should separate elements with commas");
+        assert_eq!(list!["a"].to_string(), "[a]", "Display should use the elements' Display impls");
+        assert_eq!(nl::<i32>().to_string(), "[]", "Empty list should display as []");
     }
 
     #[test]
-    fn test_into_iter_with_strings() {
-        let mut list: List<String> = nl();
-        list.push(String::from("a"));
-        list.push(String::from("b"));
-        let mut iter = list.into_iter();
-        assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should be (2, Some(2))");
-        assert_eq!(iter.next(), Some(String::from("a")), "First element should be 'a'");
-        assert_eq!(iter.next_back(), Some(String::from("b")), "Last element should be 'b'");
-        assert_eq!(iter.next(), None, "Iterator should be exhausted");
+    fn test_into_iter_as_slice_tracks_remaining() {
+        let mut iter = list![1, 2, 3, 4].into_iter();
+        assert_eq!(iter.as_slice(), &[1, 2, 3, 4]);
+        iter.next();
+        assert_eq!(iter.as_slice(), &[2, 3, 4], "as_slice should skip consumed front elements");
+        iter.next_back();
+        assert_eq!(iter.as_slice(), &[2, 3], "as_slice should skip consumed back elements");
+
+        iter.as_mut_slice()[0] = 20;
+        assert_eq!(iter.next(), Some(20), "Writes through as_mut_slice should be yielded");
+        iter.next();
+        assert!(iter.as_slice().is_empty(), "Exhausted iterator should view an empty slice");
+
+        let mut zsts = list![(); 3].into_iter();
+        zsts.next();
+        assert_eq!(zsts.as_slice().len(), 2, "ZST iterators should report the remaining count");
     }
 
     #[test]
-    fn test_into_iter_for_loop() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        let mut result = Vec::new();
-        for x in list {
-            result.push(x);
-        }
-        assert_eq!(result, vec![1, 2, 3], "For loop should yield elements in order");
+    fn test_iterators_report_exact_len() {
+        let mut into_iter = list![1, 2, 3, 4, 5].into_iter();
+        assert_eq!(into_iter.len(), 5);
+        into_iter.next();
+        into_iter.next_back();
+        assert_eq!(into_iter.len(), 3, "len should shrink from both ends");
+        into_iter.next_back();
+        assert_eq!(into_iter.count(), 2, "count should match the remaining elements");
+
+        let mut list: List<i32> = (0..6).collect();
+        let mut iter = list.iter();
+        iter.next_back();
+        assert_eq!(iter.len(), 5);
+        assert_eq!(iter.rev().enumerate().next_back(), Some((4, &0)), "rev().enumerate() should work on Iter");
+        assert_eq!(list.iter_mut().len(), 6);
+
+        let mut drain = list.drain(1..5);
+        assert_eq!(drain.len(), 4);
+        drain.next();
+        drain.next_back();
+        assert_eq!(drain.len(), 2, "Drain len should shrink from both ends");
+        assert_eq!(drain.count(), 2);
+        assert_eq!(list, [0, 5], "Counting a Drain should still drain the range");
     }
 
-    #[test]
-    fn test_into_iter_collect() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        let result: Vec<i32> = list.into_iter().collect();
-        assert_eq!(result, vec![1, 2, 3], "Collect should yield elements in order");
+    #[test]
+    fn test_exhausted_iterators_stay_exhausted() {
+        let mut iter = list![1].into_iter();
+        assert_eq!(iter.next(), Some(1));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None, "IntoIter should be fused");
+        assert_eq!(iter.next(), None, "IntoIter should be fused");
     }
 
     #[test]
-    fn test_into_iter_drop_early() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        {
-            let mut iter = list.into_iter();
-            assert_eq!(iter.next(), Some(1), "First element should be 1");
-            // Drop iterator early (before consuming all elements)
+    fn test_into_iter_nth_drops_skipped_elements() {
+        let drops = Cell::new(0);
+        let mut list: List<(i32, DropCounter)> = nl();
+        for i in 0..4 {
+            list.push((i, DropCounter(&drops)));
         }
-        // No assertion needed; test passes if no memory leaks or panics occur
+        let mut iter = list.into_iter();
+        let third = iter.nth(2).map(|(i, _)| i);
+        assert_eq!(third, Some(2), "nth(2) should return the third element");
+        assert_eq!(drops.get(), 3, "Skipped elements and the returned one should be dropped once");
+        assert_eq!(iter.len(), 1);
+        assert!(iter.nth(5).is_none(), "nth past the end should return None");
+        assert_eq!(drops.get(), 4, "nth past the end should drop the rest");
+        drop(iter);
+        assert_eq!(drops.get(), 4, "Nothing should be dropped twice");
     }
 
     #[test]
-    fn test_into_iter_size_hint_edge_cases() {
-        let mut list = nl();
-        list.push(1);
-        let mut iter = list.into_iter();
-        assert_eq!(iter.size_hint(), (1, Some(1)), "Initial size hint should be (1, Some(1))");
-        iter.next();
-        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after consuming");
-        iter.next_back();
-        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should remain (0, Some(0)) after exhaustion");
+    fn test_into_iter_and_drain_last() {
+        assert_eq!(list![0, 1, 2, 3].into_iter().last(), Some(3));
+        assert_eq!(nl::<i32>().into_iter().last(), None);
+
+        let mut strings: List<String> = ["a", "b", "c", "d"].into_iter().map(String::from).collect();
+        assert_eq!(strings.drain(..3).last(), Some(String::from("c")), "last should read from the back of the range");
+        assert_eq!(strings, ["d"]);
+        let mut zsts = list![(); 5];
+        assert_eq!(zsts.drain(..).nth(3), Some(()));
+        assert!(zsts.is_empty());
     }
 
     #[test]
-    fn test_drain_empty_list() {
-        let mut list: List<i32> = nl();
-        {
-            let mut iter = list.drain();
-            assert_eq!(iter.next(), None, "Empty iterator should yield None");
-            assert_eq!(iter.next_back(), None, "Empty iterator should yield None for next_back");
-            assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) for empty iterator");
-        }
-        list.push(3);
-        assert_eq!(list.len, 1);
+    fn test_new_in_global_behaves_like_new() {
+        let mut list = List::new_in(Global);
+        list.extend(0..10);
+        let mut tail = list.split_off(5);
+        tail.append(&mut list);
+        assert_eq!(tail, [5, 6, 7, 8, 9, 0, 1, 2, 3, 4]);
+        assert!(List::<u8>::with_capacity_in(4, Global).capacity() >= 4);
     }
 
+    #[cfg(feature = "allocator_api")]
     #[test]
-    fn test_drain_single_element() {
-        let mut list = nl();
+    fn test_custom_allocator_sees_every_allocation() {
+        use std::{alloc::{AllocError, Allocator, Global, Layout}, ptr::NonNull};
+
+        #[derive(Clone, Copy)]
+        struct Counting<'a> {
+            allocs: &'a Cell<usize>,
+            deallocs: &'a Cell<usize>,
+        }
+
+        unsafe impl Allocator for Counting<'_> {
+            fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+                self.allocs.set(self.allocs.get() + 1);
+                Global.allocate(layout)
+            }
+
+            unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+                self.deallocs.set(self.deallocs.get() + 1);
+                unsafe { Global.deallocate(ptr, layout) }
+            }
+        }
+
+        let (allocs, deallocs) = (Cell::new(0), Cell::new(0));
+        let alloc = Counting { allocs: &allocs, deallocs: &deallocs };
         {
-            list.push(42);
-            let mut iter = list.drain();
-            assert_eq!(iter.size_hint(), (1, Some(1)), "Size hint should be (1, Some(1))");
-            assert_eq!(iter.next(), Some(42), "Iterator should yield single element");
-            assert_eq!(iter.next(), None, "Iterator should be exhausted after one element");
-            assert_eq!(iter.next_back(), None, "Exhausted iterator should yield None for next_back");
-            assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after exhaustion");
+            let mut list = List::new_in(alloc);
+            for i in 0..100 {
+                list.push(i.to_string());
+            }
+            let copy = list.clone();
+            let tail = list.split_off(50);
+            assert_eq!(copy.len(), 100);
+            assert_eq!(tail.len() + list.len(), 100);
+            list.shrink_to_fit();
+
+            let mut q = crate::list::queue::Queue::new_in(alloc);
+            q.extend(0..10);
+            let (front, back) = q.into_two_lists();
+            assert_eq!(front.len() + back.len(), 10);
         }
-        list.push(3);
-        assert_eq!(list.len, 1);
+        assert!(allocs.get() > 0, "Allocations should go through the custom allocator");
+        assert_eq!(allocs.get(), deallocs.get(), "Every buffer should be freed by the allocator that made it");
     }
 
     #[test]
-    fn test_drain_multiple_elements_forward() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        {
-            let mut iter = list.drain();
-            assert_eq!(iter.size_hint(), (3, Some(3)), "Size hint should be (3, Some(3))");
-            assert_eq!(iter.next(), Some(1), "First element should be 1");
-            assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should update after next");
-            assert_eq!(iter.next(), Some(2), "Second element should be 2");
-            assert_eq!(iter.next(), Some(3), "Third element should be 3");
-            assert_eq!(iter.next(), None, "Iterator should be exhausted");
-            assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after exhaustion");
+    fn test_one_point_five_growth() {
+        let mut list = List::with_growth(GrowthStrategy::OnePointFive);
+        let mut cap = list.capacity();
+        let mut grows = 0;
+        for i in 0..10_000 {
+            list.push(i);
+            assert!(list.capacity() >= cap, "Capacity should never shrink while pushing");
+            if list.capacity() != cap {
+                grows += 1;
+                cap = list.capacity();
+            }
         }
-        list.push(3);
-        assert_eq!(list.len, 1);
+        assert!(list.capacity() * 2 <= list.len() * 3, "Capacity should stay within 1.5x of the length");
+        assert!(grows < 30, "Growth should stay geometric, grew {} times", grows);
+        assert_eq!(list.clone().capacity(), list.len());
     }
 
     #[test]
-    fn test_drain_multiple_elements_backward() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        {
-            let mut iter = list.drain();
-            assert_eq!(iter.next_back(), Some(3), "Last element should be 3");
-            assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should update after next_back");
-            assert_eq!(iter.next_back(), Some(2), "Second-to-last element should be 2");
-            assert_eq!(iter.next_back(), Some(1), "First element should be 1");
-            assert_eq!(iter.next_back(), None, "Iterator should be exhausted");
-            assert_eq!(iter.next(), None, "Exhausted iterator should yield None for next");
+    fn test_fixed_and_double_growth() {
+        let mut fixed = List::with_growth(GrowthStrategy::Fixed(4));
+        let mut caps = Vec::new();
+        for i in 0..10 {
+            fixed.push(i);
+            caps.push(fixed.capacity());
         }
-        list.push(3);
-        assert_eq!(list.len, 1);
+        assert_eq!(caps, vec![4, 4, 4, 4, 8, 8, 8, 8, 12, 12], "Fixed growth should add a constant step");
+
+        let mut doubled = List::with_growth(GrowthStrategy::Double);
+        doubled.extend(0..5);
+        assert_eq!(doubled.capacity(), 5, "extend should still reserve exactly what it needs first");
+        doubled.push(5);
+        assert_eq!(doubled.capacity(), 10);
+        fixed.reserve(100);
+        assert_eq!(fixed.capacity(), 110, "reserve should jump straight past the step when needed");
     }
 
     #[test]
-    fn test_drain_mixed_forward_backward() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        {
-            let mut iter = list.drain();
-            assert_eq!(iter.next(), Some(1), "First element should be 1");
-            assert_eq!(iter.next_back(), Some(3), "Last element should be 3");
-            assert_eq!(iter.size_hint(), (1, Some(1)), "Size hint should be (1, Some(1))");
-            assert_eq!(iter.next(), Some(2), "Middle element should be 2");
-            assert_eq!(iter.next(), None, "Iterator should be exhausted");
-            assert_eq!(iter.next_back(), None, "Exhausted iterator should yield None");
+    fn test_spare_capacity_and_set_len() {
+        let mut list: List<String> = List::new();
+        list.push("zero".to_string());
+        list.reserve(7);
+        let spare = list.spare_capacity_mut();
+        assert!(spare.len() >= 7, "Spare capacity should cover what was reserved");
+        for (i, slot) in spare.iter_mut().take(7).enumerate() {
+            slot.write((i + 1).to_string());
         }
-        list.push(3);
-        assert_eq!(list.len, 1);
+        unsafe { list.set_len(8) };
+        assert_eq!(list, ["zero", "1", "2", "3", "4", "5", "6", "7"], "Written elements should be readable after set_len");
+
+        let mut zst: List<()> = List::new();
+        assert_eq!(zst.spare_capacity_mut().len(), usize::MAX, "Zero sized types should have unlimited spare capacity");
     }
 
     #[test]
-    fn test_drain_with_strings() {
-        let mut list: List<String> = nl();
-        list.push(String::from("a"));
-        list.push(String::from("b"));
-        {
-            let mut iter = list.drain();
-            assert_eq!(iter.size_hint(), (2, Some(2)), "Size hint should be (2, Some(2))");
-            assert_eq!(iter.next(), Some(String::from("a")), "First element should be 'a'");
-            assert_eq!(iter.next_back(), Some(String::from("b")), "Last element should be 'b'");
-            assert_eq!(iter.next(), None, "Iterator should be exhausted");
+    fn test_into_boxed_slice_drops_spare_capacity() {
+        let drops = Cell::new(0);
+        let mut list = List::with_capacity(16);
+        for _ in 0..5 {
+            list.push(DropCounter(&drops));
         }
+        let boxed = list.into_boxed_slice();
+        assert_eq!(boxed.len(), 5, "The box should hold exactly len elements");
+        assert_eq!(drops.get(), 0, "Converting should not drop anything");
+        drop(boxed);
+        assert_eq!(drops.get(), 5, "Dropping the box should drop every element");
 
-        list.push(String::from("c"));
-        assert_eq!(list.len, 1);
+        let empty: Box<[String]> = List::with_capacity(4).into_boxed_slice();
+        assert!(empty.is_empty());
     }
 
     #[test]
-    fn test_drain_collect() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        let result: Vec<i32> = list.drain().collect();
-        assert_eq!(result, vec![1, 2, 3], "Collect should yield elements in order");
-        list.push(1);
-        assert_eq!(list.len, 1);
+    fn test_leak() {
+        let list = list![1, 2, 3];
+        let leaked: &'static mut [i32] = list.leak();
+        leaked[0] = 10;
+        assert_eq!(leaked, &[10, 2, 3], "The leaked slice should be writable and hold the elements");
     }
 
     #[test]
-    fn test_drain_drop_early() {
+    fn test_get_mut() {
+        let mut list = list![1, 2, 3];
+        *list.get_mut(1).unwrap() = 20;
+        assert_eq!(list.get(1), Some(&20), "get should see the change made through get_mut");
+        assert!(list.get_mut(3).is_none(), "Out of bounds get_mut should return None");
+
+        unsafe { *list.get_unchecked_mut(2) += 10 };
+        assert_eq!(list, [1, 20, 13]);
+    }
+
+    #[test]
+    fn test_insert_remove_at_the_end_matches_vec() {
         let mut list = nl();
-        list.push(1);
-        list.push(2);
-        {
-            let mut iter = list.drain();
-            assert_eq!(iter.next(), Some(1), "First element should be 1");
-            // Drop iterator early (before consuming all elements)
+        let mut oracle = Vec::new();
+        let mut next = 0;
+        // Pseudo random but deterministic mix of inserts at len and removes of the last element
+        for round in 0..500u32 {
+            if round.wrapping_mul(2654435761) % 3 != 0 || oracle.is_empty() {
+                list.insert(list.len(), next);
+                oracle.insert(oracle.len(), next);
+                next += 1;
+            } else {
+                assert_eq!(list.remove(list.len() - 1), oracle.remove(oracle.len() - 1), "Removing the last element should match Vec");
+            }
+            assert_eq!(list, oracle, "List should match Vec after round {}", round);
         }
-        // No assertion needed; test passes if no memory leaks or panics occur
+
+        while !oracle.is_empty() {
+            assert_eq!(list.remove(list.len() - 1), oracle.remove(oracle.len() - 1));
+        }
+        assert!(list.is_empty());
     }
 
     #[test]
-    fn test_drain_size_hint_edge_cases() {
+    fn test_insert_remove_boundaries() {
         let mut list = nl();
-        list.push(1);
-        let mut iter = list.drain();
-        assert_eq!(iter.size_hint(), (1, Some(1)), "Initial size hint should be (1, Some(1))");
-        iter.next();
-        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should be (0, Some(0)) after consuming");
-        iter.next_back();
-        assert_eq!(iter.size_hint(), (0, Some(0)), "Size hint should remain (0, Some(0)) after exhaustion");
+        list.insert(0, 2);
+        list.insert(0, 1);
+        list.insert(2, 3);
+        assert_eq!(list, [1, 2, 3], "Inserting at 0 and at len should both work");
+        assert_eq!(list.remove(2), 3);
+        assert_eq!(list.remove(0), 1);
+        assert_eq!(list.remove(0), 2);
+        assert!(list.is_empty());
     }
 
     #[test]
-    fn test_truncate_to_smaller_length() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        list.push(4);
-        list.push(5);
-        list.truncate(3);
-        assert_eq!(list.len, 3, "Length should be 3 after truncate");
-        assert_eq!(list.get(0), Some(&1), "First element should remain");
-        assert_eq!(list.get(1), Some(&2), "Second element should remain");
-        assert_eq!(list.get(2), Some(&3), "Third element should remain");
-        assert_eq!(list.get(3), None, "Fourth element should be discarded");
-        assert_eq!(list.get(4), None, "Fifth element should be discarded");
+    #[should_panic(expected = "index out of bounds")]
+    fn test_insert_past_len_panics() {
+        let mut list = list![1];
+        list.insert(2, 0);
     }
 
     #[test]
-    fn test_truncate_to_zero() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        list.truncate(0);
-        assert_eq!(list.len, 0, "Length should be 0 after truncate to 0");
-        assert_eq!(list.get(0), None, "List should be empty after truncate to 0");
+    fn test_rotate_in_place() {
+        let mut list = list![1, 2, 3, 4, 5];
+        list.rotate_left(2);
+        assert_eq!(list, [3, 4, 5, 1, 2], "rotate_left should bring mid to the front");
+        list.rotate_right(2);
+        assert_eq!(list, [1, 2, 3, 4, 5], "rotate_right should undo rotate_left");
+        list.rotate_left(5);
+        assert_eq!(list, [1, 2, 3, 4, 5], "Rotating by len should be a no-op");
     }
 
     #[test]
-    fn test_truncate_to_current_length() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        list.truncate(3);
-        assert_eq!(list.len, 3, "Length should remain 3");
-        assert_eq!(list.get(0), Some(&1), "First element should remain");
-        assert_eq!(list.get(1), Some(&2), "Second element should remain");
-        assert_eq!(list.get(2), Some(&3), "Third element should remain");
+    fn test_swap() {
+        let mut list = list!['a', 'b', 'c'];
+        list.swap(0, 2);
+        assert_eq!(list, ['c', 'b', 'a']);
+        list.swap(1, 1);
+        assert_eq!(list, ['c', 'b', 'a'], "Swapping an element with itself should do nothing");
     }
 
     #[test]
-    fn test_truncate_to_larger_length() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        list.truncate(10);
-        assert_eq!(list.len, 3, "Length should remain 3");
-        assert_eq!(list.get(0), Some(&1), "First element should remain");
-        assert_eq!(list.get(1), Some(&2), "Second element should remain");
-        assert_eq!(list.get(2), Some(&3), "Third element should remain");
+    #[should_panic(expected = "swap index b out of bounds: b is 3 but len is 3")]
+    fn test_swap_out_of_bounds_panics() {
+        let mut list = list![1, 2, 3];
+        list.swap(0, 3);
     }
 
     #[test]
-    fn test_truncate_empty_list() {
-        let mut list: List<i32> = nl();
-        list.truncate(0);
-        assert_eq!(list.len, 0, "Length should be 0");
-        list.truncate(5);
-        assert_eq!(list.len, 0, "Length should remain 0");
+    #[should_panic(expected = "mid out of bounds: mid is 4 but len is 3")]
+    fn test_rotate_left_out_of_bounds_panics() {
+        let mut list = list![1, 2, 3];
+        list.rotate_left(4);
     }
 
     #[test]
-    fn test_truncate_with_strings() {
-        let mut list: List<String> = nl();
-        list.push(String::from("a"));
-        list.push(String::from("b"));
-        list.push(String::from("c"));
-        list.push(String::from("d"));
-        list.truncate(2);
-        assert_eq!(list.len, 2, "Length should be 2 after truncate");
-        assert_eq!(list.get(0), Some(&String::from("a")), "First string should remain");
-        assert_eq!(list.get(1), Some(&String::from("b")), "Second string should remain");
-        assert_eq!(list.get(2), None, "Third string should be discarded");
-        // No assertion needed; test passes if no memory leaks occur
+    fn test_pop_if() {
+        let mut list = list![1, 2, 3, 4];
+        assert_eq!(list.pop_if(|x| *x % 2 == 0), Some(4), "An even tail should be popped");
+        assert_eq!(list.pop_if(|x| *x % 2 == 0), None, "An odd tail should stay");
+        assert_eq!(list, [1, 2, 3]);
+
+        let mut empty: List<i32> = nl();
+        assert_eq!(empty.pop_if(|_| true), None, "pop_if on an empty list should return None");
     }
 
     #[test]
-    fn test_truncate_preserves_capacity() {
-        let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        list.push(4);
-        let initial_cap = list.cap();
-        list.truncate(2);
-        assert_eq!(list.cap(), initial_cap, "Capacity should not change after truncate");
-        assert_eq!(list.len, 2, "Length should be 2 after truncate");
+    fn test_first_mut_last_mut() {
+        let mut list = list![1, 2, 3];
+        *list.first_mut().unwrap() = 10;
+        *list.last_mut().unwrap() = 30;
+        assert_eq!(list, [10, 2, 30]);
+
+        let mut empty: List<i32> = nl();
+        assert!(empty.first_mut().is_none());
+        assert!(empty.last_mut().is_none());
     }
 
     #[test]
-    fn test_truncate_then_push() {
+    fn test_into_iter_clone_half_consumed() {
+        let mut iter = list![1, 2, 3, 4, 5, 6].into_iter();
+        iter.next();
+        iter.next_back();
+        let cloned = iter.clone();
+        assert_eq!(cloned.len(), 4, "The clone should only hold the remaining elements");
+        assert_eq!(iter.collect::<Vec<_>>(), cloned.collect::<Vec<_>>(), "Both copies should yield the same sequence");
+
+        let strings = list!["a".to_string(), "b".to_string()].into_iter();
+        let mut cloned = strings.clone();
+        drop(strings);
+        assert_eq!(cloned.next().as_deref(), Some("a"), "The clone should outlive the original");
+    }
+
+    #[test]
+    fn test_retain_mut() {
+        let mut list = list![1, 2, 3, 4, 5];
+        list.retain_mut(|x| {
+            *x *= 2;
+            *x < 10
+        });
+        assert_eq!(list, [2, 4, 6, 8], "Kept elements should carry the predicate's changes");
+    }
+
+    #[test]
+    fn test_retain_mut_panic_keeps_list_valid() {
+        let drops = Cell::new(0);
         let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        list.truncate(1);
-        assert_eq!(list.len, 1, "Length should be 1 after truncate");
-        list.push(4);
-        assert_eq!(list.len, 2, "Length should be 2 after push");
-        assert_eq!(list.get(0), Some(&1), "First element should be 1");
-        assert_eq!(list.get(1), Some(&4), "Second element should be 4");
+        for _ in 0..5 {
+            list.push(DropCounter(&drops));
+        }
+        let mut seen = 0;
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.retain_mut(|_| {
+                seen += 1;
+                if seen == 3 {
+                    panic!("predicate panicked");
+                }
+                seen % 2 == 0
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(drops.get(), 1, "Only the rejected element should have been dropped");
+        assert_eq!(list.len(), 4, "Unvisited elements should be kept after a panic");
+        drop(list);
+        assert_eq!(drops.get(), 5, "Every element should be dropped exactly once");
     }
 
     #[test]
-    fn test_truncate_with_deref() {
+    fn test_dedup_by_case_insensitive() {
+        let mut list = list!["a".to_string(), "A".to_string(), "b".to_string()];
+        list.dedup_by(|cur, prev| cur.eq_ignore_ascii_case(prev));
+        assert_eq!(list, ["a", "b"], "The first element of each run should be kept");
+
+        let mut merged = list![(1, 1), (1, 2), (2, 3), (2, 4)];
+        merged.dedup_by(|cur, prev| {
+            if cur.0 == prev.0 {
+                prev.1 += cur.1;
+                true
+            } else {
+                false
+            }
+        });
+        assert_eq!(merged, [(1, 3), (2, 7)], "The kept element should be able to absorb the removed one");
+    }
+
+    #[test]
+    fn test_insert_slice() {
+        let mut list = list![1, 2, 3];
+        list.insert_slice(1, &[10, 11]);
+        assert_eq!(list, [1, 10, 11, 2, 3], "The slice should be inserted before index 1");
+        list.insert_slice(5, &[4]);
+        assert_eq!(list, [1, 10, 11, 2, 3, 4], "Inserting at len should append");
+        list.insert_slice(0, &[]);
+        assert_eq!(list.len(), 6, "Inserting an empty slice should do nothing");
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_insert_slice_past_len_panics() {
+        let mut list = list![1];
+        list.insert_slice(2, &[0]);
+    }
+
+    #[test]
+    fn test_insert_slice_clone_panic_keeps_list_valid() {
+        struct PanicOnClone<'a> {
+            drops: &'a Cell<usize>,
+            panics: bool,
+        }
+        impl <'a> Clone for PanicOnClone<'a> {
+            fn clone(&self) -> Self {
+                assert!(!self.panics, "clone panicked");
+                PanicOnClone { drops: self.drops, panics: false }
+            }
+        }
+        impl <'a> Drop for PanicOnClone<'a> {
+            fn drop(&mut self) {
+                self.drops.set(self.drops.get() + 1);
+            }
+        }
+
+        let drops = Cell::new(0);
         let mut list = nl();
-        list.push(1);
-        list.push(2);
-        list.push(3);
-        list.push(4);
-        list.truncate(2);
-        let slice: &[i32] = &list;
-        assert_eq!(slice, &[1, 2], "Deref should return correct slice after truncate");
+        for _ in 0..3 {
+            list.push(PanicOnClone { drops: &drops, panics: false });
+        }
+        let values = [
+            PanicOnClone { drops: &drops, panics: false },
+            PanicOnClone { drops: &drops, panics: true },
+        ];
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            list.insert_slice(1, &values);
+        }));
+        assert!(result.is_err());
+        assert_eq!(list.len(), 4, "The tail should be moved back next to the one successful clone");
+        assert_eq!(drops.get(), 0);
+        drop(list);
+        assert_eq!(drops.get(), 4, "Every live element should be dropped exactly once");
     }
 }
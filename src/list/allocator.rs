@@ -0,0 +1,75 @@
+//! The allocator the containers are parameterized over. With the nightly
+//! `allocator_api` feature this is std's `Allocator` API, otherwise it's a
+//! minimal stand-in with the same shape that only knows the global allocator,
+//! so the rest of the code doesn't have to care which one it gets.
+
+#[cfg(feature = "allocator_api")]
+pub use std::alloc::{Allocator, Global};
+
+#[cfg(not(feature = "allocator_api"))]
+pub use shim::{Allocator, Global};
+
+#[cfg(not(feature = "allocator_api"))]
+mod shim {
+    use std::{alloc::{self, Layout}, ptr::NonNull};
+
+    /// The allocator failed to hand out memory for the requested layout
+    #[derive(Debug)]
+    pub struct AllocError;
+
+    /// The subset of `std::alloc::Allocator` the containers use
+    ///
+    /// # Safety
+    ///
+    /// Same contract as `std::alloc::Allocator`: memory handed out must stay
+    /// valid until it is deallocated or reallocated through the same allocator
+    pub unsafe trait Allocator {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+
+        /// # Safety
+        ///
+        /// `ptr` must have been allocated by this allocator with `layout`
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+        /// # Safety
+        ///
+        /// `ptr` must have been allocated by this allocator with `old_layout`
+        /// and `new_layout` can't be smaller than `old_layout`
+        unsafe fn grow(&self, ptr: NonNull<u8>, old_layout: Layout, new_layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+
+        /// # Safety
+        ///
+        /// `ptr` must have been allocated by this allocator with `old_layout`
+        /// and `new_layout` can't be bigger than `old_layout`
+        unsafe fn shrink(&self, ptr: NonNull<u8>, old_layout: Layout, new_layout: Layout) -> Result<NonNull<[u8]>, AllocError>;
+    }
+
+    /// The global allocator, which is all stable Rust lets us plug in
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct Global;
+
+    impl Global {
+        fn wrap(ptr: *mut u8, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            let ptr = NonNull::new(ptr).ok_or(AllocError)?;
+            Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+        }
+    }
+
+    unsafe impl Allocator for Global {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Global::wrap(unsafe { alloc::alloc(layout) }, layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { alloc::dealloc(ptr.as_ptr(), layout) }
+        }
+
+        unsafe fn grow(&self, ptr: NonNull<u8>, old_layout: Layout, new_layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Global::wrap(unsafe { alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size()) }, new_layout)
+        }
+
+        unsafe fn shrink(&self, ptr: NonNull<u8>, old_layout: Layout, new_layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            Global::wrap(unsafe { alloc::realloc(ptr.as_ptr(), old_layout, new_layout.size()) }, new_layout)
+        }
+    }
+}
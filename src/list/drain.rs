@@ -1,19 +1,41 @@
-use std::marker::PhantomData;
+use std::{iter::FusedIterator, marker::PhantomData, ptr::NonNull};
 
-use crate::list::{List, RawValIter};
+use crate::list::{Allocator, Global, List, RawValIter};
 
-pub struct Drain<'a, T: 'a> {
-    pub(super) list: PhantomData<&'a mut List<T>>,
-    pub(super) iter: RawValIter<T>
+pub struct Drain<'a, T: 'a, A: Allocator = Global> {
+    pub(super) list: NonNull<List<T, A>>,
+    pub(super) iter: RawValIter<T>,
+    /// Where the elements after the drained range start
+    pub(super) tail_start: usize,
+    pub(super) tail_len: usize,
+    pub(super) _marker: PhantomData<&'a mut List<T, A>>,
 }
 
-impl <'a, T> Drop for Drain<'a, T> {
+unsafe impl<T: Send, A: Allocator + Send> Send for Drain<'_, T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for Drain<'_, T, A> {}
+
+impl <'a, T, A: Allocator> Drop for Drain<'a, T, A> {
     fn drop(&mut self) {
         for _ in &mut *self {}
+
+        // `List::drain` already cut the list off at the start of the range,
+        // so moving the tail down to meet it closes the gap
+        unsafe {
+            let list = self.list.as_mut();
+            let start = list.len;
+            if self.tail_start != start {
+                std::ptr::copy(
+                    list.ptr().add(self.tail_start),
+                    list.ptr().add(start),
+                    self.tail_len
+                );
+            }
+            list.len = start + self.tail_len;
+        }
     }
 }
 
-impl <'a, T> Iterator for Drain<'a, T> {
+impl <'a, T, A: Allocator> Iterator for Drain<'a, T, A> {
     type Item = T;
 
     fn next(&mut self) -> Option<T> {
@@ -23,11 +45,26 @@ impl <'a, T> Iterator for Drain<'a, T> {
     fn size_hint(&self) -> (usize, Option<usize>) {
         self.iter.size_hint()
     }
+
+    fn count(self) -> usize {
+        self.iter.size_hint().0
+    }
+
+    fn nth(&mut self, n: usize) -> Option<T> {
+        self.iter.nth(n)
+    }
+
+    fn last(mut self) -> Option<T> {
+        self.iter.next_back()
+    }
 }
 
-impl <'a, T> DoubleEndedIterator for Drain<'a, T> {
+impl <'a, T, A: Allocator> DoubleEndedIterator for Drain<'a, T, A> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.iter.next_back()
     }
 }
 
+impl <'a, T, A: Allocator> ExactSizeIterator for Drain<'a, T, A> {}
+
+impl <'a, T, A: Allocator> FusedIterator for Drain<'a, T, A> {}
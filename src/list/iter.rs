@@ -1,6 +1,6 @@
-use std::ptr::NonNull;
+use std::{iter::FusedIterator, marker::PhantomData, ptr::NonNull};
 
-use crate::list::RawList;
+use crate::list::{Allocator, Global, List, RawList};
 
 pub(super) struct RawValIter<T> {
     pub(super) front: *const T,
@@ -9,37 +9,91 @@ pub(super) struct RawValIter<T> {
 
 impl <T> RawValIter<T> {
     pub(super) unsafe fn new(slice: &[T]) -> Self {
+        unsafe { RawValIter::from_raw_parts(slice.as_ptr(), slice.len()) }
+    }
+
+    /// Same as `new` but keeps the provenance of `ptr`, so a mutable
+    /// iterator can write through the pointers it hands out
+    pub(super) unsafe fn from_raw_parts(ptr: *const T, len: usize) -> Self {
         RawValIter { 
-            front: slice.as_ptr(),
+            front: ptr,
             back: if std::mem::size_of::<T>() == 0 {
-                ((slice.as_ptr() as usize) + slice.len()) as *const _
-            } else if slice.len() == 0 {
-                slice.as_ptr()
+                ((ptr as usize) + len) as *const _
+            } else if len == 0 {
+                ptr
             } else {
                 unsafe {
-                    slice.as_ptr().add(slice.len())
+                    ptr.add(len)
                 }
             }
         }
     }
 
     pub(super) fn next(&mut self) -> Option<T> {
+        self.next_ptr().map(|ptr| unsafe { std::ptr::read(ptr) })
+    }
+
+    /// Steps past the front element and hands out a pointer to it without
+    /// reading it. Zero sized types get a dangling pointer
+    pub(super) fn next_ptr(&mut self) -> Option<*const T> {
         if self.front == self.back {
             None
         } else {
             unsafe {
                 if std::mem::size_of::<T>() == 0 {
                     self.front = (self.front as usize + 1) as *const _;
-                    Some(std::ptr::read(NonNull::dangling().as_ptr()))
+                    Some(NonNull::dangling().as_ptr())
                 } else {
-                    let val = std::ptr::read(self.front);
+                    let ptr = self.front;
                     self.front = self.front.offset(1);
-                    Some(val)
+                    Some(ptr)
                 }
             }
         }
     }
 
+    /// Drops the next `n` elements in one go and reads the one after them.
+    /// The front is moved past the skipped elements before they are dropped,
+    /// so a panicking destructor can't make them get dropped twice
+    pub(super) fn nth(&mut self, n: usize) -> Option<T> {
+        let skip = std::cmp::min(n, self.size_hint().0);
+        let skipped = std::ptr::slice_from_raw_parts_mut(self.remaining_ptr(), skip);
+        self.front = if std::mem::size_of::<T>() == 0 {
+            (self.front as usize + skip) as *const _
+        } else {
+            unsafe { self.front.add(skip) }
+        };
+        unsafe {
+            std::ptr::drop_in_place(skipped);
+        }
+        self.next()
+    }
+
+    /// Views the elements that haven't been yielded yet
+    pub(super) fn as_slice(&self) -> &[T] {
+        unsafe { std::slice::from_raw_parts(self.remaining_ptr(), self.size_hint().0) }
+    }
+
+    /// Mutable version of `as_slice`
+    ///
+    /// # Safety
+    ///
+    /// The iterator must have been built from a pointer that allows writes,
+    /// like the owned buffer behind `IntoIter`. Iterators built from a shared
+    /// slice, like the one behind `Iter`, must never call this
+    pub(super) unsafe fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { std::slice::from_raw_parts_mut(self.remaining_ptr(), self.size_hint().0) }
+    }
+
+    fn remaining_ptr(&self) -> *mut T {
+        // The front of a ZST iterator is only a counter, not an aligned pointer
+        if std::mem::size_of::<T>() == 0 {
+            NonNull::dangling().as_ptr()
+        } else {
+            self.front as *mut T
+        }
+    }
+
     pub(super) fn size_hint(&self) -> (usize, Option<usize>) {
         let elem_size = std::mem::size_of::<T>();
         let len = (self.back as usize - self.front as usize)
@@ -48,34 +102,63 @@ impl <T> RawValIter<T> {
     }
 
     pub(super) fn next_back(&mut self) -> Option<T> {
+        self.next_back_ptr().map(|ptr| unsafe { std::ptr::read(ptr) })
+    }
+
+    /// Same as `next_ptr` but from the back
+    pub(super) fn next_back_ptr(&mut self) -> Option<*const T> {
         if self.front == self.back {
             None
         } else {
             unsafe {
                 if std::mem::size_of::<T>() == 0 {
                     self.back = (self.back as usize - 1) as *const _;
-                    Some(std::ptr::read(NonNull::dangling().as_ptr()))
+                    Some(NonNull::dangling().as_ptr())
                 } else {
                     self.back = self.back.offset(-1);
-                    Some(std::ptr::read(self.back))
+                    Some(self.back)
                 }
             }
         }
     }
 }
 
-pub struct IntoIter<T> {
-    pub(super) _buf: RawList<T>,
+pub struct IntoIter<T, A: Allocator = Global> {
+    pub(super) _buf: RawList<T, A>,
     pub(super) iter: RawValIter<T>
 }
 
-impl <T> Drop for IntoIter<T> {
+impl <T, A: Allocator> IntoIter<T, A> {
+    /// Views the elements that haven't been yielded yet
+    pub fn as_slice(&self) -> &[T] {
+        self.iter.as_slice()
+    }
+
+    /// Mutably views the elements that haven't been yielded yet
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // The iterator was built from the owned buffer's pointer in `into_iter`
+        unsafe { self.iter.as_mut_slice() }
+    }
+}
+
+/// Clones the elements that haven't been yielded yet into a fresh buffer,
+/// so the clone and the original can be consumed independently
+impl <T: Clone, A: Allocator + Clone> Clone for IntoIter<T, A> {
+    fn clone(&self) -> Self {
+        let remaining = self.as_slice();
+        let mut list = List::with_capacity_in(remaining.len(), self._buf.alloc.clone());
+        list.extend_from_slice(remaining);
+        list.into_iter()
+    }
+}
+
+impl <T, A: Allocator> Drop for IntoIter<T, A> {
     fn drop(&mut self) {
         for _ in &mut *self {}
     }
 }
 
-impl <T> Iterator for IntoIter<T> {
+impl <T, A: Allocator> Iterator for IntoIter<T, A> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -85,10 +168,96 @@ impl <T> Iterator for IntoIter<T> {
     fn size_hint(&self) -> (usize, Option<usize>) {
         self.iter.size_hint()
     }
+
+    fn count(self) -> usize {
+        self.iter.size_hint().0
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n)
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
 }
 
-impl <T> DoubleEndedIterator for IntoIter<T> {
+impl <T, A: Allocator> DoubleEndedIterator for IntoIter<T, A> {
     fn next_back(&mut self) -> Option<Self::Item> {
         self.iter.next_back()
     }
 }
+
+/// Borrowing iterator over a `List`, created by `List::iter`
+pub struct Iter<'a, T> {
+    pub(super) list: PhantomData<&'a T>,
+    pub(super) iter: RawValIter<T>
+}
+
+unsafe impl<T: Sync> Send for Iter<'_, T> {}
+unsafe impl<T: Sync> Sync for Iter<'_, T> {}
+
+impl <'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_ptr().map(|ptr| unsafe { &*ptr })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.iter.size_hint().0
+    }
+}
+
+impl <'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back_ptr().map(|ptr| unsafe { &*ptr })
+    }
+}
+
+/// Mutably borrowing iterator over a `List`, created by `List::iter_mut`
+pub struct IterMut<'a, T> {
+    pub(super) list: PhantomData<&'a mut T>,
+    pub(super) iter: RawValIter<T>
+}
+
+unsafe impl<T: Send> Send for IterMut<'_, T> {}
+unsafe impl<T: Sync> Sync for IterMut<'_, T> {}
+
+impl <'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_ptr().map(|ptr| unsafe { &mut *(ptr as *mut T) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.iter.size_hint().0
+    }
+}
+
+impl <'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back_ptr().map(|ptr| unsafe { &mut *(ptr as *mut T) })
+    }
+}
+
+impl <T, A: Allocator> ExactSizeIterator for IntoIter<T, A> {}
+
+impl <T, A: Allocator> FusedIterator for IntoIter<T, A> {}
+
+impl <'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl <'a, T> FusedIterator for Iter<'a, T> {}
+
+impl <'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl <'a, T> FusedIterator for IterMut<'a, T> {}
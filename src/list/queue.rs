@@ -1,14 +1,39 @@
 use std::{
+    collections::VecDeque,
     fmt::{self, Display, Write},
-    ptr::{self}
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem,
+    ptr::{self},
+    slice
 };
 
-use crate::list::RawList;
+use crate::list::{Allocator, Global, List, RawList, RawValIter};
 
-pub struct Queue<T> {
-    buf: RawList<T>,
+/// The operations every FIFO queue backend supports, so code can be
+/// written once and run against either `Queue` or std's `VecDeque`
+pub trait QueueLike<T> {
+    /// Adds `val` at the back
+    fn enqueue(&mut self, val: T);
+    /// Removes and returns the element at the front
+    fn dequeue(&mut self) -> Option<T>;
+    /// Puts `val` back at the front, ahead of everything else
+    fn requeue(&mut self, val: T);
+    /// The element `dequeue` would return next
+    fn peek(&self) -> Option<&T>;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+pub struct Queue<T, A: Allocator = Global> {
+    buf: RawList<T, A>,
     len: usize,
     front: usize,
+    /// Maximum number of elements for a bounded queue. Kept apart from the
+    /// buffer's capacity since zero sized types always report `usize::MAX`
+    bound: Option<usize>,
 }
 
 impl <T> Queue<T> {
@@ -17,12 +42,70 @@ impl <T> Queue<T> {
             buf: RawList::new(),
             len: 0,
             front: 0,
+            bound: None,
+        }
+    }
+
+    /// Creates an empty queue with room for `cap` elements so the first
+    /// `cap` enqueues don't need to grow the buffer
+    pub fn with_capacity(cap: usize) -> Queue<T> {
+        Queue {
+            buf: RawList::with_capacity(cap),
+            len: 0,
+            front: 0,
+            bound: None,
+        }
+    }
+
+    /// Creates an empty fixed-capacity queue that holds at most `cap`
+    /// elements. Once full, `enqueue` overwrites the oldest element instead
+    /// of growing, use `try_enqueue` to refuse new elements instead.
+    pub fn bounded(cap: usize) -> Queue<T> {
+        assert!(cap != 0, "bounded queue capacity must be non-zero");
+        Queue {
+            buf: RawList::with_capacity(cap),
+            len: 0,
+            front: 0,
+            bound: Some(cap),
+        }
+    }
+
+    /// Builds a fixed-capacity queue that holds at most `cap` elements.
+    /// Once full, every new element overwrites the oldest one, so only the
+    /// last `cap` items produced by the iterator are kept.
+    pub fn from_iter_bounded<I: IntoIterator<Item = T>>(iter: I, cap: usize) -> Queue<T> {
+        let mut q = Queue::bounded(cap);
+        for val in iter {
+            q.enqueue(val);
+        }
+        q
+    }
+}
+
+impl <T, A: Allocator> Queue<T, A> {
+    /// Creates an empty queue that will allocate from `alloc`
+    pub fn new_in(alloc: A) -> Queue<T, A> {
+        Queue::with_capacity_in(0, alloc)
+    }
+
+    /// Creates an empty queue with room for `cap` elements, allocated from `alloc`
+    pub fn with_capacity_in(cap: usize, alloc: A) -> Queue<T, A> {
+        Queue {
+            buf: RawList::with_capacity_in(cap, alloc),
+            len: 0,
+            front: 0,
+            bound: None,
         }
     }
 
     pub fn enqueue(&mut self, val: T) {
         if self.is_full() {
-            self.grow()
+            if self.bound.is_some() {
+                // Bounded queues overwrite the oldest element instead of growing
+                self.dequeue();
+            } else {
+                self.grow()
+            }
         }
 
         unsafe {
@@ -32,6 +115,29 @@ impl <T> Queue<T> {
         self.len += 1;
     }
 
+    /// Enqueues `val`, and if the queue is full makes room by removing the
+    /// front element rather than growing. The displaced element is returned.
+    /// An unallocated queue still grows to fit the first element.
+    pub fn enqueue_overwrite(&mut self, val: T) -> Option<T> {
+        let displaced = if self.is_full() && self.cap() != 0 {
+            self.dequeue()
+        } else {
+            None
+        };
+        self.enqueue(val);
+        displaced
+    }
+
+    /// Enqueues `val` unless this is a full bounded queue, in which case
+    /// the value is handed back. Unbounded queues always accept it.
+    pub fn try_enqueue(&mut self, val: T) -> Result<(), T> {
+        if self.bound.is_some() && self.is_full() {
+            return Err(val);
+        }
+        self.enqueue(val);
+        Ok(())
+    }
+
     pub fn dequeue(&mut self) -> Option<T> {
         if self.len == 0 {
             return None;
@@ -44,9 +150,34 @@ impl <T> Queue<T> {
         Some(val)
     }
 
+    /// Removes the front element and refills its slot with the back
+    /// element, so `front` stays where it is and the queue shrinks from the
+    /// back instead. This costs one extra copy over `dequeue`, and the
+    /// previous back element becomes the new front.
+    pub fn swap_dequeue(&mut self) -> Option<T> {
+        if self.len <= 1 {
+            return self.dequeue();
+        }
+        self.len -= 1;
+        unsafe {
+            let front = self.ptr().add(self.front);
+            let val = ptr::read(front);
+            ptr::copy_nonoverlapping(self.ptr().add(self.back()), front, 1);
+            Some(val)
+        }
+    }
+
     pub fn requeue(&mut self, val: T) {
         if self.is_full() {
-            self.grow()
+            if self.bound.is_some() {
+                // Make room at the front by discarding the newest element
+                self.len -= 1;
+                unsafe {
+                    ptr::drop_in_place(self.ptr().add(self.back()));
+                }
+            } else {
+                self.grow()
+            }
         }
         self.decr_front();
         unsafe {
@@ -55,6 +186,115 @@ impl <T> Queue<T> {
         self.len += 1;
     }
 
+    /// Keeps only the elements matching the predicate, in their dequeue
+    /// order, and returns how many were removed. Capacity is unchanged.
+    pub fn retain_reporting<F: FnMut(&T) -> bool>(&mut self, mut f: F) -> usize {
+        let len = self.len;
+        // Cycle every element once through the ring. Each dequeue frees
+        // the slot the following enqueue needs, so this never grows.
+        for _ in 0..len {
+            if let Some(val) = self.dequeue() && f(&val) {
+                self.enqueue(val);
+            }
+        }
+        len - self.len
+    }
+
+    /// Drops every element and moves the front back to the start of the
+    /// buffer. The allocation is kept for reuse.
+    pub fn clear(&mut self) {
+        // [c, b:d, junk, f:a, b] drops [a, b] then [c, d]
+        let front_len = std::cmp::min(self.len, self.cap() - self.front);
+        let front = ptr::slice_from_raw_parts_mut(unsafe { self.ptr().add(self.front) }, front_len);
+        let wrapped = ptr::slice_from_raw_parts_mut(self.ptr(), self.len - front_len);
+
+        // Forget the elements first so a panicking destructor leaks the rest
+        // instead of dropping anything twice
+        self.len = 0;
+        self.front = 0;
+        unsafe {
+            ptr::drop_in_place(front);
+            ptr::drop_in_place(wrapped);
+        }
+    }
+
+    /// Removes every element and yields them in dequeue order. The queue is
+    /// left empty with `front` reset to 0 as soon as this is called, and
+    /// anything the `QueueDrain` doesn't yield is dropped along with it
+    pub fn drain(&mut self) -> QueueDrain<'_, T, A> {
+        let front = self.front;
+        let len = self.len;
+        // Forget the elements up front so leaking the drain leaks them
+        // instead of leaving the queue pointing at moved-out slots
+        self.len = 0;
+        self.front = 0;
+        QueueDrain {
+            queue: self,
+            front,
+            len,
+        }
+    }
+
+    /// Drops every element and refills the queue with `count` fresh ones
+    /// from `f`. The existing buffer is reused, only growing when `count`
+    /// is bigger than the current capacity.
+    pub fn reset_with<F: FnMut() -> T>(&mut self, count: usize, mut f: F) {
+        self.clear();
+        if self.bound.is_none() {
+            self.reserve(count);
+        }
+        for _ in 0..count {
+            self.enqueue(f());
+        }
+    }
+
+    /// Consumes the queue and splits it into the first `index` elements and
+    /// the rest, both in dequeue order starting from the front of their own
+    /// buffer. The halves are ordinary growable queues.
+    pub fn split_at(mut self, index: usize) -> (Queue<T, A>, Queue<T, A>) where A: Clone {
+        assert!(index <= self.len, "index out of bounds");
+        let len = self.len;
+        let alloc = self.buf.alloc.clone();
+        let live = self.make_contiguous().as_ptr();
+        let (first, second) = unsafe {
+            (
+                Queue::moved_from(live, index, alloc.clone()),
+                Queue::moved_from(live.add(index), len - index, alloc)
+            )
+        };
+        // Ownership of the elements moved to the new queues
+        self.len = 0;
+        (first, second)
+    }
+
+    /// Consumes the queue and returns its two physical runs as lists: the
+    /// elements from the front up to the end of the buffer, then the ones
+    /// that wrapped around. The second list is empty if nothing wrapped.
+    pub fn into_two_lists(mut self) -> (List<T, A>, List<T, A>) where A: Clone {
+        let front_len = std::cmp::min(self.len, self.cap() - self.front);
+        let back_len = self.len - front_len;
+        let lists = unsafe {
+            (
+                List::moved_from(self.ptr().add(self.front), front_len, self.buf.alloc.clone()),
+                List::moved_from(self.ptr(), back_len, self.buf.alloc.clone())
+            )
+        };
+        // Ownership of the elements moved to the lists
+        self.len = 0;
+        lists
+    }
+
+    /// Builds an unbounded queue by moving `len` elements out of `src`.
+    /// The caller must not use or drop the source elements afterwards.
+    unsafe fn moved_from(src: *const T, len: usize, alloc: A) -> Queue<T, A> {
+        let mut q = Queue::with_capacity_in(len, alloc);
+        q.len = len;
+        unsafe {
+            ptr::copy_nonoverlapping(src, q.ptr(), len);
+        }
+        q
+    }
+
     pub fn peek(&self) -> Option<&T> {
         if self.len == 0 {
             None
@@ -66,39 +306,186 @@ impl <T> Queue<T> {
         }
     }
 
+    /// Returns the element `index` positions from the front, so `get(0)`
+    /// is the same as `peek`
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            None
+        } else {
+            unsafe {
+                Some(&*self.ptr().add(self.physical_index(index)))
+            }
+        }
+    }
+
+    pub fn contains(&self, x: &T) -> bool where T: PartialEq {
+        self.iter().any(|val| val == x)
+    }
+
+    /// Returns the element that would be removed by `dequeue_back`
+    pub fn peek_back(&self) -> Option<&T> {
+        if self.len == 0 {
+            None
+        } else {
+            unsafe {
+                Some(&*self.ptr().add(self.physical_index(self.len - 1)))
+            }
+        }
+    }
+
+    /// Removes the most recently enqueued element. The front stays where it is
+    pub fn dequeue_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        unsafe {
+            Some(ptr::read(self.ptr().add(self.back())))
+        }
+    }
+
+    /// Iterates from front to back for as long as `pred` holds, without
+    /// removing anything from the queue
+    pub fn iter_while<'a, F: FnMut(&T) -> bool + 'a>(&'a self, mut pred: F) -> impl Iterator<Item = &'a T> {
+        self.iter().take_while(move |val| pred(val))
+    }
+
+    /// Returns the first element in dequeue order that satisfies `pred`
+    pub fn find<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Option<&T> {
+        self.iter().find(|val| pred(val))
+    }
+
+    /// Iterates over the elements in dequeue order without removing them
+    pub fn iter(&self) -> QueueIter<'_, T, A> {
+        QueueIter {
+            queue: self,
+            start: 0,
+            end: self.len,
+        }
+    }
+
+    /// Returns the longest run of live elements that starts at the front
+    /// and is contiguous in memory, stopping where the ring wraps around
+    pub fn front_contiguous_mut(&mut self) -> &mut [T] {
+        let run = std::cmp::min(self.len, self.cap() - self.front);
+        unsafe {
+            slice::from_raw_parts_mut(self.ptr().add(self.front), run)
+        }
+    }
+
+    /// Returns the live elements as two slices in dequeue order: the run
+    /// from the front to the end of the buffer, then the part that wrapped
+    /// around to the start. The second slice is empty if nothing wrapped
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let front_len = std::cmp::min(self.len, self.cap() - self.front);
+        unsafe {
+            (
+                slice::from_raw_parts(self.ptr().add(self.front), front_len),
+                slice::from_raw_parts(self.ptr(), self.len - front_len),
+            )
+        }
+    }
+
+    /// Moves the live elements into one run at the start of the buffer and
+    /// returns them as a slice in dequeue order
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        let front_len = self.cap() - self.front;
+        if self.len > front_len {
+            // [5, b:6, junk, f:3, 4] -> [5, 6, 3, 4, junk] -> [f:3, 4, 5, b:6, junk]
+            let back_len = self.len - front_len;
+            unsafe {
+                ptr::copy(self.ptr().add(self.front), self.ptr().add(back_len), front_len);
+                slice::from_raw_parts_mut(self.ptr(), self.len).rotate_left(back_len);
+            }
+        } else if self.front != 0 {
+            // [junk, f:3, 4, b:5, junk] -> [f:3, 4, b:5, junk, junk]
+            unsafe {
+                ptr::copy(self.ptr().add(self.front), self.ptr(), self.len);
+            }
+        }
+        self.front = 0;
+        unsafe {
+            slice::from_raw_parts_mut(self.ptr(), self.len)
+        }
+    }
+
     pub fn size(&self) -> usize {
         self.len
     }
 
+    /// Number of elements in the queue. Same as `size`
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of elements the queue can hold without reallocating, or the
+    /// bound of a bounded queue. Unbounded zero sized types report `usize::MAX`
+    pub fn capacity(&self) -> usize {
+        self.bound.unwrap_or(self.cap())
+    }
+
+    /// Number of bytes reserved by the backing buffer, used or not.
+    /// Always 0 for zero sized types
+    pub fn allocated_bytes(&self) -> usize {
+        self.cap() * std::mem::size_of::<T>()
+    }
+
+    /// Ensures there is room for at least `additional` more elements
+    /// without reallocating.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self.len.checked_add(additional).expect("capacity overflow");
+        if required <= self.cap() {
+            return;
+        }
+        let old_cap = self.cap();
+        self.buf.grow_to(required);
+        self.relocate_after_grow(old_cap);
+    }
+
     fn grow(&mut self) {
-        // Need to shuffle shorter of two splits
-        let front_len = self.cap() - self.front;
-        let back_len = self.cap() - front_len;
+        // The relocation needs the old capacity, `self.cap()` is already
+        // the new one once the buffer has grown
+        let old_cap = self.cap();
         self.buf.grow();
+        self.relocate_after_grow(old_cap);
+    }
+
+    /// Fixes up a queue that wrapped around the end of the old buffer so that
+    /// it is a valid ring again in the bigger one. Segment lengths come from
+    /// `front` and `len` so this works whether or not the queue was full.
+    fn relocate_after_grow(&mut self, old_cap: usize) {
         // [5, b:6, f:3, 4, junk, junk, junk, junk]
-        if self.front == 0 {
+        let front_len = old_cap - self.front;
+        if self.len <= front_len {
+            // Live elements never wrapped so they are still in order
             return;
         }
-        if front_len < back_len {
+        let back_len = self.len - front_len;
+
+        // Need to shuffle shorter of two splits, as long as the back one fits after the old end
+        if front_len < back_len || old_cap + back_len > self.cap() {
             // shuffle front chunk to back of new array
             // [5, b:6, junk, junk, junk, junk, f:3, 4]
             let new_front = self.cap() - front_len;
             unsafe {
-                std::ptr::copy::<T>(
+                ptr::copy(
                     self.ptr().add(self.front),
                     self.ptr().add(new_front),
                     front_len
-                    );
+                );
             }
             self.front = new_front
         } else {
             // shuffle back to right after front
             // [junk, junk, f:3, 4, 5, b:6, junk, junk]
-            let shuffle_index = self.front + front_len;
             unsafe {
-                ptr::copy(
+                ptr::copy_nonoverlapping(
                     self.ptr(),
-                    self.ptr().add(shuffle_index),
+                    self.ptr().add(old_cap),
                     back_len
                 )
             }
@@ -129,7 +516,12 @@ impl <T> Queue<T> {
     }
 
     fn is_full(&self) -> bool {
-        self.len == self.cap()
+        self.len == self.capacity()
+    }
+
+    /// Maps a position counted from the front of the queue to its slot in the buffer
+    fn physical_index(&self, i: usize) -> usize {
+        (self.front + i) % self.cap()
     }
 
     fn back(&self) -> usize {
@@ -137,132 +529,486 @@ impl <T> Queue<T> {
     }
 }
 
-impl <T> Drop for Queue<T> {
-    fn drop(&mut self) {
-        while let Some(_) = self.dequeue() { }
+impl <T: Ord, A: Allocator> Queue<T, A> {
+    /// Consumes the queue and returns its elements sorted in ascending
+    /// order. The original queue order is not preserved
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut v = Vec::with_capacity(self.len);
+        while let Some(val) = self.dequeue() {
+            v.push(val);
+        }
+        v.sort();
+        v
     }
-}
 
-impl <T: Display> Display for Queue<T> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_char('[')?;
-        for i in self.front..self.len {
-            let val = unsafe {
-                ptr::read(self.buf.ptr.as_ptr().add(i))
-            };
-            f.write_str(&val.to_string()[..])?;
-            if i != self.len - 1 {
-                f.write_char(',')?;
-            }
+    /// Empties the queue, yielding its elements in ascending order. Elements
+    /// that aren't consumed are dropped along with the iterator
+    pub fn drain_sorted(&mut self) -> impl Iterator<Item = T> + '_ {
+        let live = self.make_contiguous();
+        live.sort();
+        let iter = unsafe { RawValIter::new(live) };
+
+        // The iterator owns the elements now, so the queue is left empty
+        // even if the iterator is leaked
+        self.len = 0;
+        self.front = 0;
+
+        DrainSorted::<T, A> {
+            iter,
+            queue: PhantomData,
         }
-        f.write_char(']')
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    extern crate stats_alloc;
+struct DrainSorted<'a, T: 'a, A: Allocator> {
+    queue: PhantomData<&'a mut Queue<T, A>>,
+    iter: RawValIter<T>
+}
 
-    use stats_alloc::{Region, StatsAlloc, INSTRUMENTED_SYSTEM};
-    use std::alloc::System;
+impl <'a, T, A: Allocator> Drop for DrainSorted<'a, T, A> {
+    fn drop(&mut self) {
+        for _ in &mut *self {}
+    }
+}
 
-    #[global_allocator]
-    static GLOBAL: &StatsAlloc<System> = &INSTRUMENTED_SYSTEM;
+impl <'a, T, A: Allocator> Iterator for DrainSorted<'a, T, A> {
+    type Item = T;
 
-    // Helper function to create a new queue
-    fn nq<T>() -> Queue<T> {
-        Queue::new()
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
     }
 
-    #[test]
-    fn test_new_queue_is_empty() {
-        let q: Queue<i32> = nq();
-        assert!(q.peek().is_none(), "New queue should be empty");
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
     }
+}
 
-    #[test]
-    fn test_enqueue_single_element() {
-        let mut q = nq();
-        q.enqueue(42);
-        assert_eq!(q.peek(), Some(&42), "Peek should return the enqueued element");
+/// Borrowing iterator over a `Queue` in dequeue order, created by `Queue::iter`
+pub struct QueueIter<'a, T, A: Allocator = Global> {
+    queue: &'a Queue<T, A>,
+    /// Logical positions still to be yielded, counted from the front
+    start: usize,
+    end: usize,
+}
+
+impl <'a, T, A: Allocator> QueueIter<'a, T, A> {
+    fn get(&self, i: usize) -> &'a T {
+        unsafe { &*self.queue.ptr().add(self.queue.physical_index(i)) }
     }
+}
 
-    #[test]
-    fn test_enqueue_dequeue_single_element() {
-        let mut q = nq();
-        q.enqueue(42);
-        assert_eq!(q.dequeue(), Some(42), "Dequeue should return the enqueued element");
-        assert!(q.peek().is_none(), "Queue should be empty after dequeue");
+impl <'a, T, A: Allocator> Iterator for QueueIter<'a, T, A> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.start == self.end {
+            return None;
+        }
+        let val = self.get(self.start);
+        self.start += 1;
+        Some(val)
     }
 
-    #[test]
-    fn test_enqueue_multiple_elements() {
-        let mut q = nq();
-        q.enqueue(1);
-        q.enqueue(2);
-        q.enqueue(3);
-        assert_eq!(q.peek(), Some(&1), "Peek should return first element");
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
     }
+}
 
-    #[test]
-    fn test_dequeue_multiple_elements() {
-        let mut q = nq();
-        q.enqueue(1);
-        q.enqueue(2);
-        q.enqueue(3);
-        assert_eq!(q.dequeue(), Some(1), "First dequeue should return 1");
-        assert_eq!(q.dequeue(), Some(2), "Second dequeue should return 2");
-        assert_eq!(q.dequeue(), Some(3), "Third dequeue should return 3");
-        assert_eq!(q.dequeue(), None, "Dequeue on empty queue should return None");
+impl <'a, T, A: Allocator> DoubleEndedIterator for QueueIter<'a, T, A> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(self.get(self.end))
     }
+}
 
-    #[test]
-    fn test_requeue_single_element() {
-        let mut q = nq();
-        q.enqueue(1);
-        q.dequeue();
-        q.requeue(2);
-        assert_eq!(q.peek(), Some(&2), "Requeue should place element at front");
-        assert_eq!(q.dequeue(), Some(2), "Dequeue should return requeued element");
+impl <'a, T, A: Allocator> ExactSizeIterator for QueueIter<'a, T, A> {}
+
+impl <'a, T, A: Allocator> IntoIterator for &'a Queue<T, A> {
+    type Item = &'a T;
+
+    type IntoIter = QueueIter<'a, T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
+}
 
-    #[test]
-    fn test_requeue_after_enqueue() {
-        let mut q = nq();
-        q.enqueue(1);
-        q.enqueue(2);
-        q.requeue(0);
-        assert_eq!(q.peek(), Some(&0), "Requeue should place element at front");
-        assert_eq!(q.dequeue(), Some(0), "First dequeue should return requeued element");
-        assert_eq!(q.dequeue(), Some(1), "Second dequeue should return original first element");
+/// Owning iterator over a `Queue` in dequeue order, created by `into_iter`.
+/// Frees the buffer once dropped, dropping anything it didn't yield
+pub struct QueueIntoIter<T, A: Allocator = Global> {
+    buf: RawList<T, A>,
+    front: usize,
+    len: usize,
+}
+
+impl <T, A: Allocator> Drop for QueueIntoIter<T, A> {
+    fn drop(&mut self) {
+        for _ in &mut *self {}
     }
+}
 
-    #[test]
-    fn test_peek_empty_queue() {
-        let q: Queue<i32> = nq();
-        assert_eq!(q.peek(), None, "Peek on empty queue should return None");
+impl <T, A: Allocator> Iterator for QueueIntoIter<T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let val = unsafe { ptr::read(self.buf.ptr.as_ptr().add(self.front)) };
+        self.front = (self.front + 1) % self.buf.cap;
+        self.len -= 1;
+        Some(val)
     }
 
-    #[test]
-    fn test_dequeue_empty_queue() {
-        let mut q: Queue<i32> = nq();
-        assert_eq!(q.dequeue(), None, "Dequeue on empty queue should return None");
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
     }
+}
 
-    #[test]
-    fn test_queue_with_strings() {
-        let mut q: Queue<String> = nq();
-        q.enqueue(String::from("hello"));
-        q.enqueue(String::from("world"));
-        assert_eq!(q.peek(), Some(&String::from("hello")), "Peek should return first string");
+impl <T, A: Allocator> DoubleEndedIterator for QueueIntoIter<T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let back = (self.front + self.len) % self.buf.cap;
+        unsafe { Some(ptr::read(self.buf.ptr.as_ptr().add(back))) }
+    }
+}
+
+impl <T, A: Allocator> ExactSizeIterator for QueueIntoIter<T, A> {}
+
+/// Draining iterator over a `Queue` in dequeue order, created by
+/// `Queue::drain`. Drops whatever it didn't yield when dropped
+pub struct QueueDrain<'a, T, A: Allocator = Global> {
+    queue: &'a mut Queue<T, A>,
+    /// Physical index of the next element at the front
+    front: usize,
+    len: usize,
+}
+
+impl <'a, T, A: Allocator> Drop for QueueDrain<'a, T, A> {
+    fn drop(&mut self) {
+        for _ in &mut *self {}
+    }
+}
+
+impl <'a, T, A: Allocator> Iterator for QueueDrain<'a, T, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let val = unsafe { ptr::read(self.queue.ptr().add(self.front)) };
+        self.front = (self.front + 1) % self.queue.cap();
+        self.len -= 1;
+        Some(val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl <'a, T, A: Allocator> DoubleEndedIterator for QueueDrain<'a, T, A> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let back = (self.front + self.len) % self.queue.cap();
+        unsafe { Some(ptr::read(self.queue.ptr().add(back))) }
+    }
+}
+
+impl <'a, T, A: Allocator> ExactSizeIterator for QueueDrain<'a, T, A> {}
+
+impl <'a, T, A: Allocator> FusedIterator for QueueDrain<'a, T, A> {}
+
+impl <T, A: Allocator> IntoIterator for Queue<T, A> {
+    type Item = T;
+
+    type IntoIter = QueueIntoIter<T, A>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        unsafe {
+            let buf = ptr::read(&self.buf);
+            let iter = QueueIntoIter {
+                buf,
+                front: self.front,
+                len: self.len,
+            };
+            mem::forget(self);
+            iter
+        }
+    }
+}
+
+impl <T, A: Allocator> Drop for Queue<T, A> {
+    fn drop(&mut self) {
+        while self.dequeue().is_some() { }
+    }
+}
+
+impl <T: Clone, A: Allocator + Clone> Clone for Queue<T, A> {
+    /// Clones the elements in dequeue order into a new buffer starting at
+    /// index 0. A bounded queue stays bounded with the same capacity
+    fn clone(&self) -> Self {
+        // `enqueue` bumps len after every write, so if a `clone` panics the
+        // partial queue only drops the elements that were actually cloned
+        let cap = self.bound.unwrap_or(self.len);
+        let mut q = Queue::with_capacity_in(cap, self.buf.alloc.clone());
+        q.bound = self.bound;
+        for val in self.iter() {
+            q.enqueue(val.clone());
+        }
+        q
+    }
+}
+
+impl <T> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut q = Queue::new();
+        q.extend(iter);
+        q
+    }
+}
+
+impl <T, A: Allocator> Extend<T> for Queue<T, A> {
+    /// Enqueues every item in order. Bounded queues keep their capacity and
+    /// overwrite the oldest elements as usual
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        if self.bound.is_none() {
+            self.reserve(iter.size_hint().0);
+        }
+        for val in iter {
+            self.enqueue(val);
+        }
+    }
+}
+
+/// Queues are equal when they hold equal elements in the same dequeue
+/// order, wherever those elements sit in the buffer
+impl <T: PartialEq, A: Allocator> PartialEq for Queue<T, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl <T: Eq, A: Allocator> Eq for Queue<T, A> {}
+
+impl <T, A: Allocator> QueueLike<T> for Queue<T, A> {
+    fn enqueue(&mut self, val: T) {
+        Queue::enqueue(self, val)
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        Queue::dequeue(self)
+    }
+
+    fn requeue(&mut self, val: T) {
+        Queue::requeue(self, val)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        Queue::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        Queue::len(self)
+    }
+}
+
+impl <T> QueueLike<T> for VecDeque<T> {
+    fn enqueue(&mut self, val: T) {
+        self.push_back(val)
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+
+    fn requeue(&mut self, val: T) {
+        self.push_front(val)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.front()
+    }
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+}
+
+impl <T: fmt::Debug, A: Allocator> fmt::Debug for Queue<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl <T: Display, A: Allocator> Display for Queue<T, A> {
+    /// Prints the elements in dequeue order, like `[a, b, c]`, the same
+    /// way `List` does
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('[')?;
+        for i in 0..self.len {
+            // Format through a reference, reading the value out would
+            // drop a second copy of it
+            let val = unsafe {
+                &*self.ptr().add(self.physical_index(i))
+            };
+            val.fmt(f)?;
+            if i != self.len - 1 {
+                f.write_str(", ")?;
+            }
+        }
+        f.write_char(']')
+    }
+}
+
+#[cfg(feature = "serde")]
+impl <T: serde::Serialize, A: Allocator> serde::Serialize for Queue<T, A> {
+    /// Serializes as a sequence in dequeue order
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl <'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Queue<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct QueueVisitor<T>(PhantomData<T>);
+
+        impl <'de, T: serde::Deserialize<'de>> serde::de::Visitor<'de> for QueueVisitor<T> {
+            type Value = Queue<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a sequence")
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                // Same cap on the hint as `List`, a hostile input shouldn't
+                // get to pick how much we allocate up front
+                let mut q = Queue::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                while let Some(val) = seq.next_element()? {
+                    q.enqueue(val);
+                }
+                Ok(q)
+            }
+        }
+
+        deserializer.deserialize_seq(QueueVisitor(PhantomData))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    extern crate stats_alloc;
+
+    use stats_alloc::Region;
+    use crate::list::tests::{DropCounter, GLOBAL};
+
+    // Helper function to create a new queue
+    fn nq<T>() -> Queue<T> {
+        Queue::new()
+    }
+
+    #[test]
+    fn test_new_queue_is_empty() {
+        let q: Queue<i32> = nq();
+        assert!(q.peek().is_none(), "New queue should be empty");
+    }
+
+    #[test]
+    fn test_enqueue_single_element() {
+        let mut q = nq();
+        q.enqueue(42);
+        assert_eq!(q.peek(), Some(&42), "Peek should return the enqueued element");
+    }
+
+    #[test]
+    fn test_enqueue_dequeue_single_element() {
+        let mut q = nq();
+        q.enqueue(42);
+        assert_eq!(q.dequeue(), Some(42), "Dequeue should return the enqueued element");
+        assert!(q.peek().is_none(), "Queue should be empty after dequeue");
+    }
+
+    #[test]
+    fn test_enqueue_multiple_elements() {
+        let mut q = nq();
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(3);
+        assert_eq!(q.peek(), Some(&1), "Peek should return first element");
+    }
+
+    #[test]
+    fn test_dequeue_multiple_elements() {
+        let mut q = nq();
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(3);
+        assert_eq!(q.dequeue(), Some(1), "First dequeue should return 1");
+        assert_eq!(q.dequeue(), Some(2), "Second dequeue should return 2");
+        assert_eq!(q.dequeue(), Some(3), "Third dequeue should return 3");
+        assert_eq!(q.dequeue(), None, "Dequeue on empty queue should return None");
+    }
+
+    #[test]
+    fn test_requeue_single_element() {
+        let mut q = nq();
+        q.enqueue(1);
+        q.dequeue();
+        q.requeue(2);
+        assert_eq!(q.peek(), Some(&2), "Requeue should place element at front");
+        assert_eq!(q.dequeue(), Some(2), "Dequeue should return requeued element");
+    }
+
+    #[test]
+    fn test_requeue_after_enqueue() {
+        let mut q = nq();
+        q.enqueue(1);
+        q.enqueue(2);
+        q.requeue(0);
+        assert_eq!(q.peek(), Some(&0), "Requeue should place element at front");
+        assert_eq!(q.dequeue(), Some(0), "First dequeue should return requeued element");
+        assert_eq!(q.dequeue(), Some(1), "Second dequeue should return original first element");
+    }
+
+    #[test]
+    fn test_peek_empty_queue() {
+        let q: Queue<i32> = nq();
+        assert_eq!(q.peek(), None, "Peek on empty queue should return None");
+    }
+
+    #[test]
+    fn test_dequeue_empty_queue() {
+        let mut q: Queue<i32> = nq();
+        assert_eq!(q.dequeue(), None, "Dequeue on empty queue should return None");
+    }
+
+    #[test]
+    fn test_queue_with_strings() {
+        let mut q: Queue<String> = nq();
+        q.enqueue(String::from("hello"));
+        q.enqueue(String::from("world"));
+        assert_eq!(q.peek(), Some(&String::from("hello")), "Peek should return first string");
         assert_eq!(q.dequeue(), Some(String::from("hello")), "Dequeue should return first string");
         assert_eq!(q.peek(), Some(&String::from("world")), "Peek should return second string");
     }
 
     #[test]
     fn ensure_heap_allocated_queue_items_are_dropped_if_left_in_queue() {
-        let reg = Region::new(&GLOBAL);
+        let reg = Region::new(GLOBAL);
         {
             let mut q: Queue<String> = nq();
             q.enqueue(String::from("hello"));
@@ -436,4 +1182,913 @@ mod tests {
         assert_eq!(Some(1), q.dequeue());
         assert_eq!(Some(2), q.dequeue());
     }
+
+    #[test]
+    fn from_iter_bounded_keeps_last_cap_items() {
+        let mut q = Queue::from_iter_bounded(0..10, 3);
+        assert_eq!(3, q.size());
+        assert_eq!(3, q.cap());
+        assert_eq!(Some(7), q.dequeue());
+        assert_eq!(Some(8), q.dequeue());
+        assert_eq!(Some(9), q.dequeue());
+        assert_eq!(None, q.dequeue());
+    }
+
+    #[test]
+    fn bounded_queue_overwrites_oldest_on_enqueue() {
+        let mut q = Queue::from_iter_bounded(vec![String::from("a"), String::from("b")], 2);
+        q.enqueue(String::from("c"));
+        assert_eq!(2, q.size());
+        assert_eq!(2, q.cap());
+        assert_eq!(Some(String::from("b")), q.dequeue());
+        assert_eq!(Some(String::from("c")), q.dequeue());
+    }
+
+    #[test]
+    fn reserve_on_partially_wrapped_queue() {
+        let mut q = nq();
+        for i in 1..=8 {
+            q.enqueue(i);
+        }
+        for _ in 0..6 {
+            q.dequeue();
+        } // [junk, junk, junk, junk, junk, junk, f:7, b:8]
+        q.enqueue(9);
+        q.enqueue(10); // [9, b:10, junk, junk, junk, junk, f:7, 8]
+        assert_eq!(4, q.size());
+        assert_eq!(8, q.cap());
+
+        q.reserve(20);
+        assert!(q.cap() >= 24, "Capacity should fit len + additional");
+        assert_eq!(4, q.size());
+        assert_eq!(Some(&7), q.peek());
+        for i in 11..=30 {
+            q.enqueue(i);
+        }
+        assert!(q.cap() < 48, "Reserved capacity should not need to grow again");
+        for i in 7..=30 {
+            assert_eq!(Some(i), q.dequeue());
+        }
+        assert_eq!(None, q.dequeue());
+    }
+
+    #[test]
+    fn reserve_moves_front_chunk_when_back_is_longer() {
+        let mut q = nq();
+        for i in 1..=8 {
+            q.enqueue(i);
+        }
+        for _ in 0..7 {
+            q.dequeue();
+        } // [junk, junk, junk, junk, junk, junk, junk, f:8]
+        q.enqueue(9);
+        q.enqueue(10);
+        q.enqueue(11); // [9, 10, b:11, junk, junk, junk, junk, f:8]
+        q.reserve(1);
+        assert_eq!(8, q.cap(), "Reserve should not grow when there is room");
+        q.reserve(10);
+        assert_eq!(16, q.cap());
+        assert_eq!(15, q.front, "Shorter front chunk should move to the end");
+        for i in 8..=11 {
+            assert_eq!(Some(i), q.dequeue());
+        }
+        assert_eq!(None, q.dequeue());
+    }
+
+    #[test]
+    fn reserve_on_contiguous_queue_keeps_order() {
+        let mut q = nq();
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(3); // [f:1, 2, b:3, junk]
+        q.dequeue();
+        q.reserve(10);
+        assert!(q.cap() >= 12);
+        assert_eq!(Some(2), q.dequeue());
+        assert_eq!(Some(3), q.dequeue());
+        assert_eq!(None, q.dequeue());
+    }
+
+    #[test]
+    fn into_sorted_vec_sorts_live_elements() {
+        let mut q = nq();
+        q.enqueue(5);
+        q.enqueue(1);
+        q.enqueue(4);
+        q.dequeue();
+        q.enqueue(3);
+        q.requeue(9);
+        q.enqueue(2);
+        assert_eq!(vec![1, 2, 3, 4, 9], q.into_sorted_vec());
+    }
+
+    #[test]
+    fn retain_reporting_on_wrapped_queue() {
+        let mut q = nq();
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(3);
+        q.enqueue(4); // [f:1, 2, 3, b:4]
+        q.dequeue();
+        q.dequeue(); // [junk, junk, f:3, b:4]
+        q.enqueue(5);
+        q.enqueue(6); // [5, b:6, f:3, 4]
+        let removed = q.retain_reporting(|x| x % 2 == 0);
+        assert_eq!(2, removed);
+        assert_eq!(2, q.size());
+        assert_eq!(4, q.cap(), "Capacity should be unchanged");
+        assert_eq!(Some(4), q.dequeue());
+        assert_eq!(Some(6), q.dequeue());
+        assert_eq!(None, q.dequeue());
+    }
+
+    #[test]
+    fn swap_dequeue_moves_back_to_front() {
+        let mut q = nq();
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(3);
+        assert_eq!(Some(1), q.swap_dequeue());
+        assert_eq!(2, q.size());
+        assert_eq!(Some(&3), q.peek(), "Previous back element should now be at the front");
+        assert_eq!(Some(3), q.dequeue());
+        assert_eq!(Some(2), q.swap_dequeue());
+        assert_eq!(None, q.swap_dequeue());
+    }
+
+    #[test]
+    fn swap_dequeue_on_wrapped_queue() {
+        let mut q: Queue<String> = nq();
+        for s in ["a", "b", "c", "d"] {
+            q.enqueue(String::from(s));
+        }
+        q.dequeue();
+        q.dequeue();
+        q.enqueue(String::from("e")); // [b:e, junk, f:c, d]
+        assert_eq!(Some(String::from("c")), q.swap_dequeue());
+        assert_eq!(Some(&String::from("e")), q.peek());
+        assert_eq!(Some(String::from("e")), q.dequeue());
+        assert_eq!(Some(String::from("d")), q.dequeue());
+        assert_eq!(None, q.dequeue());
+    }
+
+    #[test]
+    fn allocated_bytes_follows_capacity() {
+        let mut q: Queue<u32> = nq();
+        assert_eq!(0, q.allocated_bytes());
+        q.enqueue(1);
+        assert_eq!(4, q.allocated_bytes());
+        q.enqueue(2);
+        q.enqueue(3);
+        assert_eq!(16, q.allocated_bytes());
+        q.reserve(10);
+        assert_eq!(q.cap() * 4, q.allocated_bytes());
+        let zst: Queue<()> = nq();
+        assert_eq!(0, zst.allocated_bytes());
+    }
+
+    #[test]
+    fn iter_while_stops_at_first_failure() {
+        let mut q = nq();
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(9);
+        q.enqueue(3);
+        let prefix: Vec<&i32> = q.iter_while(|x| *x < 5).collect();
+        assert_eq!(vec![&1, &2], prefix);
+        assert_eq!(4, q.size(), "iter_while should not remove anything");
+        assert_eq!(Some(&1), q.peek());
+    }
+
+    #[test]
+    fn iter_while_follows_wrap_around() {
+        let mut q = nq();
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(3);
+        q.enqueue(4);
+        q.dequeue();
+        q.dequeue();
+        q.enqueue(5); // [b:5, junk, f:3, 4]
+        let all: Vec<&i32> = q.iter_while(|_| true).collect();
+        assert_eq!(vec![&3, &4, &5], all);
+    }
+
+    #[test]
+    fn find_searches_wrapped_segment() {
+        let mut q = nq();
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(3);
+        q.enqueue(4);
+        q.dequeue();
+        q.dequeue();
+        q.enqueue(5);
+        q.enqueue(6); // [5, b:6, f:3, 4]
+        assert_eq!(Some(&5), q.find(|x| *x > 4), "Should find the first match after the wrap");
+        assert_eq!(Some(&3), q.find(|x| x % 2 == 1), "Should honor dequeue order");
+        assert_eq!(None, q.find(|x| *x > 6));
+    }
+
+    #[test]
+    fn drain_sorted_yields_ascending_and_empties_queue() {
+        let mut q = nq();
+        q.enqueue(3);
+        q.enqueue(1);
+        q.enqueue(2);
+        let sorted: Vec<i32> = q.drain_sorted().collect();
+        assert_eq!(vec![1, 2, 3], sorted);
+        assert_eq!(0, q.size());
+        assert_eq!(None, q.dequeue());
+        q.enqueue(4);
+        assert_eq!(Some(4), q.dequeue(), "Queue should be usable after draining");
+    }
+
+    #[test]
+    fn drain_sorted_on_wrapped_queue_dropped_early() {
+        let mut q: Queue<String> = nq();
+        for s in ["d", "a", "c", "b"] {
+            q.enqueue(String::from(s));
+        }
+        q.dequeue();
+        q.enqueue(String::from("e")); // [b:e, f:a, c, b]
+        let mut drain = q.drain_sorted();
+        assert_eq!(Some(String::from("a")), drain.next());
+        assert_eq!(Some(String::from("b")), drain.next());
+        drop(drain);
+        assert_eq!(0, q.size(), "Queue should be empty after an early drop");
+        q.enqueue(String::from("f"));
+        assert_eq!(Some(&String::from("f")), q.peek());
+    }
+
+    #[test]
+    fn reset_with_reuses_buffer() {
+        let mut q = nq();
+        for i in 0..8 {
+            q.enqueue(i);
+        }
+        q.dequeue();
+        q.dequeue();
+        let ptr = q.ptr();
+
+        let reg = Region::new(GLOBAL);
+        q.reset_with(8, || 7);
+        let change = reg.change();
+
+        assert_eq!(0, change.allocations, "Refilling within capacity should not allocate");
+        assert_eq!(0, change.reallocations, "Refilling within capacity should not reallocate");
+        assert_eq!(ptr, q.ptr(), "Buffer should be reused");
+        assert_eq!(8, q.cap());
+        assert_eq!(8, q.size());
+        assert_eq!(0, q.front);
+        assert_eq!(0, q.retain_reporting(|x| *x == 7), "Every element should come from f");
+    }
+
+    #[test]
+    fn reset_with_grows_past_capacity() {
+        let mut q: Queue<String> = nq();
+        q.enqueue(String::from("old"));
+        let mut n = 0;
+        q.reset_with(3, || { n += 1; n.to_string() });
+        assert_eq!(3, q.size());
+        assert!(q.cap() >= 3);
+        assert_eq!(Some(String::from("1")), q.dequeue());
+        assert_eq!(Some(String::from("2")), q.dequeue());
+        assert_eq!(Some(String::from("3")), q.dequeue());
+    }
+
+    #[test]
+    fn split_at_wrapped_queue() {
+        let mut q: Queue<String> = nq();
+        for s in ["x", "x", "a", "b"] {
+            q.enqueue(String::from(s));
+        }
+        q.dequeue();
+        q.dequeue();
+        for s in ["c", "d", "e"] {
+            q.enqueue(String::from(s));
+        } // wrapped around before growing
+        assert_eq!(5, q.size());
+        let (mut first, mut second) = q.split_at(2);
+        assert_eq!(2, first.size());
+        assert_eq!(3, second.size());
+        assert_eq!(0, first.front);
+        assert_eq!(0, second.front);
+        assert_eq!(Some(String::from("a")), first.dequeue());
+        assert_eq!(Some(String::from("b")), first.dequeue());
+        assert_eq!(None, first.dequeue());
+        assert_eq!(Some(String::from("c")), second.dequeue());
+        second.enqueue(String::from("f"));
+        assert_eq!(Some(String::from("d")), second.dequeue());
+        assert_eq!(Some(String::from("e")), second.dequeue());
+        assert_eq!(Some(String::from("f")), second.dequeue());
+    }
+
+    #[test]
+    fn split_at_ends() {
+        let q = Queue::from_iter_bounded(1..=3, 3);
+        let (first, second) = q.split_at(0);
+        assert_eq!(0, first.size());
+        assert_eq!(3, second.size());
+        let (mut first, second) = second.split_at(3);
+        assert_eq!(3, first.size());
+        assert_eq!(0, second.size());
+        first.enqueue(4);
+        assert_eq!(4, first.size(), "Split halves should grow instead of overwriting");
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn split_at_out_of_bounds() {
+        let mut q = nq();
+        q.enqueue(1);
+        let _ = q.split_at(2);
+    }
+
+    #[test]
+    fn front_contiguous_mut_stops_at_wrap() {
+        let mut q = nq();
+        q.enqueue(1);
+        q.enqueue(2);
+        q.enqueue(3);
+        q.enqueue(4);
+        q.dequeue();
+        q.dequeue();
+        q.enqueue(5);
+        q.enqueue(6); // [5, b:6, f:3, 4]
+        let run = q.front_contiguous_mut();
+        assert_eq!(&[3, 4], run, "Run should stop at the end of the buffer");
+        run[0] = 30;
+        assert_eq!(Some(30), q.dequeue(), "Edits should be visible through the queue");
+        assert_eq!(&[4], q.front_contiguous_mut());
+        q.dequeue();
+        assert_eq!(&[5, 6], q.front_contiguous_mut(), "Run should cover everything once unwrapped");
+        let mut empty: Queue<i32> = nq();
+        assert!(empty.front_contiguous_mut().is_empty());
+    }
+
+    #[test]
+    fn into_two_lists_contiguous() {
+        let drops = std::cell::Cell::new(0);
+        let mut q = nq();
+        for i in 0..3 {
+            q.enqueue((i, DropCounter(&drops)));
+        }
+        q.dequeue();
+        assert_eq!(1, drops.get());
+        let (front, back) = q.into_two_lists();
+        let ids: Vec<i32> = front.iter().map(|e| e.0).collect();
+        assert_eq!(vec![1, 2], ids);
+        assert_eq!(0, back.len(), "Nothing wrapped so the second list should be empty");
+        assert_eq!(1, drops.get(), "Moving into lists should not drop anything");
+        drop(front);
+        drop(back);
+        assert_eq!(3, drops.get());
+    }
+
+    #[test]
+    fn into_two_lists_wrapped() {
+        let drops = std::cell::Cell::new(0);
+        let mut q = nq();
+        for i in 1..=4 {
+            q.enqueue((i, DropCounter(&drops)));
+        }
+        q.dequeue();
+        q.dequeue();
+        q.enqueue((5, DropCounter(&drops)));
+        q.enqueue((6, DropCounter(&drops))); // [5, b:6, f:3, 4]
+        let (front, back) = q.into_two_lists();
+        let front_ids: Vec<i32> = front.iter().map(|e| e.0).collect();
+        let back_ids: Vec<i32> = back.iter().map(|e| e.0).collect();
+        assert_eq!(vec![3, 4], front_ids);
+        assert_eq!(vec![5, 6], back_ids);
+        assert_eq!(2, drops.get(), "Only the dequeued elements should be dropped so far");
+        drop(front);
+        drop(back);
+        assert_eq!(6, drops.get());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_uses_dequeue_order() {
+        let mut q = Queue::from_iter_bounded(0..3, 3);
+        q.enqueue(3);
+        // [3, f:1, 2]
+        let json = serde_json::to_string(&q).unwrap();
+        assert_eq!("[1,2,3]", json);
+
+        let mut back: Queue<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(Some(1), back.dequeue());
+        assert_eq!(Some(2), back.dequeue());
+        assert_eq!(Some(3), back.dequeue());
+        assert_eq!(None, back.dequeue());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_deserializes_into_a_single_buffer() {
+        let reg = Region::new(GLOBAL);
+        let q: Queue<i32> = serde_json::from_str("[1,2,3,4,5]").unwrap();
+        let change = reg.change();
+        assert_eq!(1, change.allocations, "Deserializing should fill the queue's own buffer, not a temporary one");
+        assert_eq!(0, change.deallocations);
+        assert_eq!(vec![1, 2, 3, 4, 5], q.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn len_is_empty_and_capacity() {
+        let mut q = nq();
+        assert!(q.is_empty());
+        assert_eq!(0, q.len());
+        assert_eq!(0, q.capacity());
+
+        for i in 0..3 {
+            q.enqueue(i);
+        }
+        q.dequeue();
+        q.dequeue();
+        q.enqueue(3);
+        // [junk, junk, f:2, b:3]
+        assert!(!q.is_empty());
+        assert_eq!(2, q.len());
+        assert_eq!(q.size(), q.len());
+        assert_eq!(4, q.capacity());
+
+        q.dequeue();
+        q.dequeue();
+        assert!(q.is_empty());
+        assert_eq!(4, q.capacity(), "Dequeuing should keep the allocation");
+        assert_eq!(usize::MAX, nq::<()>().capacity());
+    }
+
+    #[test]
+    fn display_follows_wrap_around_without_copying() {
+        let mut q = nq();
+        for s in ["a", "b", "c", "d"] {
+            q.enqueue(String::from(s));
+        }
+        q.dequeue();
+        q.dequeue();
+        q.enqueue(String::from("e"));
+        // Slot 0 holds e, slot 1 is empty since a and b were dequeued,
+        // and the front is c in slot 2
+        // [b:e, junk, f:c, d]
+        assert_eq!(4, q.cap());
+        assert_eq!(2, q.front, "The front should be past the two dequeued slots");
+
+        let reg = Region::new(GLOBAL);
+        let printed = format!("{}", q);
+        let change = reg.change();
+        assert_eq!("[c, d, e]", printed);
+        assert_eq!(change.allocations, change.deallocations + 1, "Only the formatted String should still be allocated");
+
+        assert_eq!("[]", nq::<i32>().to_string());
+        drop(printed);
+    }
+
+    #[test]
+    fn iter_follows_wrap_around_without_dequeuing() {
+        let mut q = nq();
+        for i in 0..4 {
+            q.enqueue(i);
+        }
+        q.dequeue();
+        q.dequeue();
+        q.enqueue(4);
+        q.enqueue(5);
+        // [4, b:5, f:2, 3]
+
+        let mut iter = q.iter();
+        assert_eq!(4, iter.len());
+        assert_eq!(Some(&2), iter.next());
+        assert_eq!(Some(&5), iter.next_back());
+        assert_eq!(2, iter.len());
+
+        let mut seen = Vec::new();
+        for val in &q {
+            seen.push(*val);
+        }
+        assert_eq!(4, q.len(), "Iterating should not remove anything");
+        let mut dequeued = Vec::new();
+        while let Some(val) = q.dequeue() {
+            dequeued.push(val);
+        }
+        assert_eq!(dequeued, seen);
+        assert_eq!(0, q.iter().count());
+    }
+
+    #[test]
+    fn into_iter_collects_wrapped_queue_in_order() {
+        let mut q = nq();
+        for s in ["a", "b", "c", "d"] {
+            q.enqueue(String::from(s));
+        }
+        q.dequeue();
+        q.enqueue(String::from("e"));
+        // [b:e, f:b, c, d]
+        let collected: Vec<String> = q.into_iter().collect();
+        assert_eq!(vec!["b", "c", "d", "e"], collected);
+
+        let q = Queue::from_iter_bounded(0..6, 4);
+        // [4, b:5, f:2, 3]
+        let mut iter = q.into_iter();
+        assert_eq!(4, iter.len());
+        assert_eq!(Some(5), iter.next_back());
+        assert_eq!(Some(2), iter.next());
+        assert_eq!(Some(4), iter.next_back());
+        assert_eq!(vec![3], iter.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn into_iter_drops_unyielded_elements_once() {
+        let drops = std::cell::Cell::new(0);
+        let q = Queue::from_iter_bounded((0..5).map(|_| DropCounter(&drops)), 3);
+        assert_eq!(2, drops.get());
+        let mut iter = q.into_iter();
+        drop(iter.next());
+        assert_eq!(3, drops.get());
+        drop(iter);
+        assert_eq!(5, drops.get(), "Remaining elements should be dropped with the iterator");
+    }
+
+    #[test]
+    fn clear_wrapped_queue_keeps_allocation() {
+        let drops = std::cell::Cell::new(0);
+        let reg = Region::new(GLOBAL);
+        {
+            let mut q = nq();
+            for s in ["a", "b", "c", "d"] {
+                q.enqueue((String::from(s), DropCounter(&drops)));
+            }
+            q.dequeue();
+            q.dequeue();
+            q.enqueue((String::from("e"), DropCounter(&drops)));
+            // [b:e, junk, f:c, d]
+            assert_eq!(2, drops.get());
+
+            q.clear();
+            assert_eq!(5, drops.get(), "clear should drop every live element once");
+            assert_eq!(0, q.len());
+            assert_eq!(4, q.capacity(), "clear should keep the allocation");
+            assert!(q.peek().is_none());
+
+            q.enqueue((String::from("f"), DropCounter(&drops)));
+            assert_eq!(Some("f"), q.peek().map(|(s, _)| s.as_str()));
+        }
+        let change = reg.change();
+        assert_eq!(6, drops.get());
+        assert_eq!(change.allocations, change.deallocations);
+    }
+
+    #[test]
+    fn peek_back_and_dequeue_back() {
+        let mut q = nq();
+        assert_eq!(None, q.peek_back());
+        assert_eq!(None, q.dequeue_back());
+        q.enqueue(1);
+        q.enqueue(2);
+        q.requeue(0);
+        // [1, b:2, junk, f:0]
+        assert_eq!(Some(&2), q.peek_back());
+        assert_eq!(Some(2), q.dequeue_back());
+        assert_eq!(Some(1), q.dequeue_back());
+        assert_eq!(Some(&0), q.peek_back(), "Back should wrap to the front segment");
+        assert_eq!(Some(&0), q.peek());
+        assert_eq!(Some(0), q.dequeue_back());
+        assert_eq!(None, q.peek_back());
+    }
+
+    #[test]
+    fn deque_operations_match_vec_deque() {
+        let mut q = nq();
+        let mut oracle = std::collections::VecDeque::new();
+        // Small xorshift so the sequence is varied but reproducible
+        let mut seed: u32 = 0x2545_f491;
+        for i in 0..2000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            match seed % 4 {
+                0 => { q.enqueue(i); oracle.push_back(i); }
+                1 => { q.requeue(i); oracle.push_front(i); }
+                2 => assert_eq!(oracle.pop_front(), q.dequeue()),
+                _ => assert_eq!(oracle.pop_back(), q.dequeue_back()),
+            }
+            assert_eq!(oracle.len(), q.len());
+            assert_eq!(oracle.front(), q.peek());
+            assert_eq!(oracle.back(), q.peek_back());
+        }
+        assert!(oracle.iter().eq(q.iter()));
+    }
+
+    #[test]
+    fn with_capacity_then_reserve_on_wrapped_queue() {
+        let mut q = Queue::with_capacity(5);
+        assert_eq!(5, q.capacity());
+        for i in 0..5 {
+            q.enqueue(i);
+        }
+        assert_eq!(5, q.capacity(), "Filling up to the initial capacity should not grow");
+        q.dequeue();
+        q.dequeue();
+        q.enqueue(5);
+        q.enqueue(6);
+        // [5, b:6, f:2, 3, 4]
+        q.reserve(10);
+        assert!(q.capacity() >= 15);
+        q.enqueue(7);
+        let order: Vec<i32> = q.into_iter().collect();
+        assert_eq!(vec![2, 3, 4, 5, 6, 7], order);
+
+        assert_eq!(0, Queue::<i32>::with_capacity(0).capacity());
+    }
+
+    #[test]
+    fn queues_compare_in_dequeue_order() {
+        let mut fresh = nq();
+        for i in 2..6 {
+            fresh.enqueue(i);
+        }
+        let wrapped = Queue::from_iter_bounded(0..6, 4);
+        // [4, b:5, f:2, 3]
+        assert_eq!(fresh, wrapped, "Same logical contents should be equal regardless of front");
+        assert_eq!("[2, 3, 4, 5]", format!("{:?}", wrapped));
+
+        fresh.dequeue_back();
+        assert_ne!(fresh, wrapped, "Different lengths should not be equal");
+        fresh.enqueue(6);
+        assert_ne!(fresh, wrapped, "A differing element should not be equal");
+        assert_eq!(nq::<i32>(), nq::<i32>());
+    }
+
+    #[test]
+    fn as_slices_and_make_contiguous_on_wrapped_queue() {
+        let mut q = Queue::from_iter_bounded(0..6, 4);
+        // [4, b:5, f:2, 3]
+        assert_eq!((&[2, 3][..], &[4, 5][..]), q.as_slices());
+
+        let live = q.make_contiguous();
+        assert_eq!(&[2, 3, 4, 5], live);
+        live[0] = 20;
+        assert_eq!((&[20, 3, 4, 5][..], &[][..]), q.as_slices());
+        q.enqueue(6);
+        assert_eq!(vec![3, 4, 5, 6], q.iter().copied().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn make_contiguous_moves_unwrapped_run_to_start() {
+        let mut q = Queue::with_capacity(5);
+        for s in ["a", "b", "c", "d"] {
+            q.enqueue(String::from(s));
+        }
+        q.dequeue();
+        // [junk, f:b, c, b:d, junk]
+        let (front, wrapped) = q.as_slices();
+        assert_eq!(["b", "c", "d"], front);
+        assert!(wrapped.is_empty());
+
+        assert_eq!(["b", "c", "d"], q.make_contiguous());
+        assert_eq!(0, q.front, "make_contiguous should move the run to the start");
+        let empty: Queue<String> = nq();
+        let (front, wrapped) = empty.as_slices();
+        assert!(front.is_empty() && wrapped.is_empty());
+    }
+
+    #[test]
+    fn clone_wrapped_queue_is_equal_but_independent() {
+        let mut q: Queue<String> = Queue::from_iter_bounded((0..6).map(|i| i.to_string()), 4);
+        // [4, b:5, f:2, 3]
+        let mut copy = q.clone();
+        assert_eq!(q, copy);
+        assert_eq!(0, copy.front, "The clone should start at the front of its buffer");
+
+        copy.dequeue();
+        q.enqueue(String::from("6"));
+        assert_eq!(vec!["3", "4", "5"], copy.iter().collect::<Vec<_>>());
+        assert_eq!(vec!["3", "4", "5", "6"], q.iter().collect::<Vec<_>>());
+
+        copy.enqueue(String::from("x"));
+        copy.enqueue(String::from("y"));
+        assert_eq!(4, copy.len(), "A clone of a bounded queue should stay bounded");
+    }
+
+    #[test]
+    fn clone_drops_partial_queue_on_panic() {
+        let drops = std::cell::Cell::new(0);
+        struct Fussy<'a>(u32, DropCounter<'a>);
+        impl Clone for Fussy<'_> {
+            fn clone(&self) -> Self {
+                assert!(self.0 != 2, "clone failed");
+                Fussy(self.0, DropCounter(self.1.0))
+            }
+        }
+
+        let mut q = nq();
+        for i in 0..4 {
+            q.enqueue(Fussy(i, DropCounter(&drops)));
+        }
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| q.clone()));
+        assert!(result.is_err());
+        assert_eq!(2, drops.get(), "Only the two finished clones should be dropped");
+        drop(q);
+        assert_eq!(6, drops.get());
+    }
+
+    #[test]
+    fn collect_and_extend() {
+        let mut q: Queue<i32> = (0..5).collect();
+        assert_eq!(5, q.capacity(), "Collecting should reserve from the size hint");
+        for i in 0..5 {
+            assert_eq!(Some(i), q.dequeue());
+        }
+        assert_eq!(None, q.dequeue());
+
+        q.extend(vec![7, 8]);
+        q.extend((9..12).filter(|i| i % 2 == 1));
+        assert_eq!(vec![7, 8, 9, 11], q.into_iter().collect::<Vec<_>>());
+
+        let mut bounded = Queue::from_iter_bounded(0..2, 2);
+        bounded.extend(2..10);
+        assert_eq!(2, bounded.capacity(), "Extending a bounded queue should not grow it");
+        assert_eq!(vec![8, 9], bounded.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn growth_patterns_match_vec_deque() {
+        // Each seed drives a different mix of operations, all biased towards
+        // growing so the wrapped relocation runs at many fill levels
+        for seed in 1..=64u32 {
+            let mut q = nq();
+            let mut oracle = std::collections::VecDeque::new();
+            let mut state = seed.wrapping_mul(0x9e37_79b9) | 1;
+            for i in 0..300 {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                match state % 8 {
+                    0..=2 => { q.enqueue(i); oracle.push_back(i); }
+                    3 | 4 => { q.requeue(i); oracle.push_front(i); }
+                    5 => assert_eq!(oracle.pop_front(), q.dequeue()),
+                    6 => assert_eq!(oracle.pop_back(), q.dequeue_back()),
+                    _ => q.reserve((state % 7) as usize),
+                }
+                assert!(oracle.iter().eq(q.iter()), "seed {} diverged at step {}", seed, i);
+            }
+            assert!(q.capacity() >= q.len());
+        }
+    }
+
+    #[test]
+    fn get_and_contains_across_wrap() {
+        let q = Queue::from_iter_bounded(0..6, 4);
+        // [4, b:5, f:2, 3]
+        assert_eq!(q.peek(), q.get(0));
+        assert_eq!(Some(&3), q.get(1));
+        assert_eq!(Some(&4), q.get(2), "get should follow the wrap to the start of the buffer");
+        assert_eq!(Some(&5), q.get(3));
+        assert_eq!(None, q.get(4));
+
+        assert!(q.contains(&2));
+        assert!(q.contains(&5));
+        assert!(!q.contains(&1), "Overwritten elements should not be found");
+        assert!(!nq().contains(&0));
+    }
+
+    #[test]
+    fn bounded_keeps_last_cap_values() {
+        let mut q = Queue::bounded(3);
+        assert_eq!(3, q.capacity());
+        for i in 0..3 {
+            assert_eq!(None, q.enqueue_overwrite(i));
+        }
+        assert_eq!(Some(0), q.enqueue_overwrite(3), "A full queue should hand back the oldest element");
+        assert_eq!(Some(1), q.enqueue_overwrite(4));
+        assert_eq!(Err(5), q.try_enqueue(5), "try_enqueue should refuse when a bounded queue is full");
+        assert_eq!(vec![2, 3, 4], q.iter().copied().collect::<Vec<_>>());
+        assert_eq!(3, q.capacity(), "A bounded queue should never grow");
+
+        q.dequeue();
+        assert_eq!(Ok(()), q.try_enqueue(5));
+        assert_eq!(vec![3, 4, 5], q.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn enqueue_overwrite_on_unbounded_queue() {
+        let mut q = nq();
+        assert_eq!(None, q.enqueue_overwrite(1), "An unallocated queue should grow for its first element");
+        q.enqueue(2);
+        assert_eq!(Some(1), q.enqueue_overwrite(3), "A full unbounded queue should overwrite instead of growing");
+        assert_eq!(2, q.capacity());
+        assert_eq!(Ok(()), q.try_enqueue(4), "Unbounded queues should always accept");
+        assert_eq!(vec![2, 3, 4], q.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "bounded queue capacity must be non-zero")]
+    fn bounded_zero_capacity_panics() {
+        let _q: Queue<i32> = Queue::bounded(0);
+    }
+
+    #[test]
+    fn bounded_zero_sized_type() {
+        let mut q: Queue<()> = Queue::bounded(3);
+        assert_eq!(3, q.capacity(), "A bounded ZST queue should report its bound");
+        for _ in 0..3 {
+            assert_eq!(Ok(()), q.try_enqueue(()));
+        }
+        assert_eq!(Err(()), q.try_enqueue(()), "try_enqueue should refuse once the bound is reached");
+        q.enqueue(());
+        assert_eq!(3, q.len(), "enqueue should overwrite instead of going past the bound");
+        assert_eq!(3, q.clone().capacity(), "A clone should keep the bound");
+
+        let q = Queue::from_iter_bounded(std::iter::repeat_n((), 10), 4);
+        assert_eq!(4, q.len());
+        assert_eq!(4, q.capacity());
+    }
+
+    #[test]
+    fn drain_wrapped_queue_partially() {
+        let reg = Region::new(GLOBAL);
+        {
+            let mut q: Queue<String> = Queue::with_capacity(4);
+            for s in ["a", "b", "c", "d"] {
+                q.enqueue(s.to_string());
+            }
+            q.dequeue();
+            q.dequeue();
+            q.enqueue("e".to_string());
+            q.enqueue("f".to_string());
+            // [e, b:f, f:c, d]
+
+            let mut taken = Vec::new();
+            for s in q.drain() {
+                taken.push(s);
+                if taken.len() == 2 {
+                    break;
+                }
+            }
+            assert_eq!(vec!["c", "d"], taken, "Drain should start at the logical front");
+            assert!(q.is_empty(), "Queue should be empty once the drain is dropped");
+            assert_eq!(0, q.front);
+            assert_eq!(4, q.capacity(), "Draining should keep the buffer");
+
+            q.enqueue("g".to_string());
+            assert_eq!(vec!["g"], q.drain().collect::<Vec<_>>());
+        }
+        let change = reg.change();
+        assert_eq!(change.allocations, change.deallocations, "Every undrained String should be freed");
+    }
+
+    #[test]
+    fn drain_drops_remaining_exactly_once() {
+        let drops = std::cell::Cell::new(0);
+        let mut q = nq();
+        for _ in 0..3 {
+            q.enqueue(DropCounter(&drops));
+        }
+        q.dequeue();
+        for _ in 0..3 {
+            q.enqueue(DropCounter(&drops));
+        }
+        assert_eq!(1, drops.get());
+
+        let mut drain = q.drain();
+        assert_eq!(5, drain.len());
+        drop(drain.next_back());
+        drop(drain);
+        assert_eq!(6, drops.get(), "Every element should be dropped exactly once");
+        assert!(q.is_empty());
+    }
+
+    // Runs the same FIFO scenario against any backend
+    fn exercise<Q: QueueLike<i32>>(q: &mut Q) {
+        assert!(q.is_empty());
+        assert_eq!(None, q.dequeue());
+        for i in 1..=5 {
+            q.enqueue(i);
+        }
+        assert_eq!(5, q.len());
+        assert_eq!(Some(&1), q.peek());
+        assert_eq!(Some(1), q.dequeue());
+        assert_eq!(Some(2), q.dequeue());
+        q.requeue(0);
+        assert_eq!(Some(&0), q.peek(), "Requeued element should be at the front");
+        for i in 6..=10 {
+            q.enqueue(i);
+        }
+
+        let mut order = Vec::new();
+        while let Some(val) = q.dequeue() {
+            order.push(val);
+        }
+        assert_eq!(vec![0, 3, 4, 5, 6, 7, 8, 9, 10], order);
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn queue_like_backends_agree() {
+        exercise(&mut nq());
+        exercise(&mut Queue::with_capacity(2));
+        exercise(&mut VecDeque::new());
+    }
 }
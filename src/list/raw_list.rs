@@ -1,62 +1,210 @@
 use std::{alloc::{self, Layout}, ptr::NonNull};
 
-pub(super) struct RawList<T> {
+use crate::list::{GrowthStrategy, TryReserveError, allocator::{Allocator, Global}};
+
+pub(super) struct RawList<T, A: Allocator = Global> {
     pub(super) ptr: NonNull<T>,
-    pub(super) cap: usize
+    pub(super) cap: usize,
+    pub(super) alloc: A,
+    pub(super) growth: GrowthStrategy,
 }
 
-unsafe impl<T: Send> Send for RawList<T> {}
-unsafe impl<T: Sync> Sync for RawList<T> {}
+unsafe impl<T: Send, A: Allocator + Send> Send for RawList<T, A> {}
+unsafe impl<T: Sync, A: Allocator + Sync> Sync for RawList<T, A> {}
 
 impl <T> RawList<T> {
     pub fn new() -> RawList<T> {
+        RawList::new_in(Global)
+    }
+
+    /// Allocates room for exactly `cap` elements up front. A `cap` of zero
+    /// or a zero sized `T` behaves the same as `new()`.
+    pub(super) fn with_capacity(cap: usize) -> RawList<T> {
+        RawList::with_capacity_in(cap, Global)
+    }
+}
+
+impl <T, A: Allocator> RawList<T, A> {
+    pub(super) fn new_in(alloc: A) -> RawList<T, A> {
         let cap = if std::mem::size_of::<T>() == 0 { usize::MAX } else { 0 };
         RawList { 
             ptr: NonNull::dangling(),
             cap,
+            alloc,
+            growth: GrowthStrategy::Double,
         }
     }
 
+    /// Same as `with_capacity` but allocates from `alloc`
+    pub(super) fn with_capacity_in(cap: usize, alloc: A) -> RawList<T, A> {
+        if std::mem::size_of::<T>() == 0 || cap == 0 {
+            return RawList::new_in(alloc);
+        }
+
+        let layout = Layout::array::<T>(cap).expect("capacity overflow");
+        assert!(layout.size() <= isize::MAX as usize, "Allocation too large");
+
+        let ptr = match alloc.allocate(layout) {
+            Ok(p) => p.cast(),
+            Err(_) => alloc::handle_alloc_error(layout),
+        };
+        RawList { ptr, cap, alloc, growth: GrowthStrategy::Double }
+    }
+
+    /// Grows the buffer by one step of its growth strategy, or allocates
+    /// room for one element if there is no buffer yet
     pub(super) fn grow(&mut self) {
         assert!(std::mem::size_of::<T>() != 0, "capacity overflow");
+        self.grow_to(self.cap.checked_add(1).expect("capacity overflow"));
+    }
 
-        let (new_cap, new_layout) = if self.cap == 0 {
-            (1, Layout::array::<T>(1).unwrap())
-        } else {
-            let new_cap = 2 * self.cap;
-            let new_layout = Layout::array::<T>(new_cap).unwrap();
-            (new_cap, new_layout)
-        };
+    /// Grows the buffer so it can hold at least `min_cap` elements in a
+    /// single reallocation, going further if the growth strategy asks for
+    /// it so growth stays amortized. Does nothing if there is already enough room.
+    pub(super) fn grow_to(&mut self, min_cap: usize) {
+        if min_cap <= self.cap {
+            return;
+        }
+        self.grow_exact(std::cmp::max(min_cap, self.growth.next_cap(self.cap)));
+    }
+
+    /// Fallible version of `grow_to`.
+    pub(super) fn try_grow_to(&mut self, min_cap: usize) -> Result<(), TryReserveError> {
+        if min_cap <= self.cap {
+            return Ok(());
+        }
+        self.try_grow_exact(std::cmp::max(min_cap, self.growth.next_cap(self.cap)))
+    }
+
+    /// Grows the buffer to exactly `new_cap` elements. Does nothing if the
+    /// buffer is already at least that big.
+    pub(super) fn grow_exact(&mut self, new_cap: usize) {
+        match self.try_grow_exact(new_cap) {
+            Ok(()) => {}
+            Err(TryReserveError::CapacityOverflow) => panic!("capacity overflow"),
+            Err(TryReserveError::AllocError { layout }) => alloc::handle_alloc_error(layout),
+        }
+    }
+
+    /// Fallible version of `grow_exact`. Reports capacity overflow and
+    /// allocator failure instead of panicking or aborting.
+    pub(super) fn try_grow_exact(&mut self, new_cap: usize) -> Result<(), TryReserveError> {
+        if new_cap <= self.cap {
+            return Ok(());
+        }
+        if std::mem::size_of::<T>() == 0 {
+            return Err(TryReserveError::CapacityOverflow);
+        }
 
-        // Ensure that the new allocation doesn't exceed `isize::MAX` bytes.
-        assert!(new_layout.size() <= isize::MAX as usize, "Allocation too large");
+        // `Layout::array` also rejects allocations over `isize::MAX` bytes.
+        let new_layout = Layout::array::<T>(new_cap).map_err(|_| TryReserveError::CapacityOverflow)?;
 
         let new_ptr = if self.cap == 0 {
-            unsafe { alloc::alloc(new_layout) }
+            self.alloc.allocate(new_layout)
         } else {
             let old_layout = Layout::array::<T>(self.cap).unwrap();
-            let old_ptr = self.ptr.as_ptr() as *mut u8;
-            unsafe { alloc::realloc(old_ptr, old_layout, new_layout.size()) }
+            unsafe { self.alloc.grow(self.ptr.cast(), old_layout, new_layout) }
         };
 
-        // if allocation fails, `new_ptr` will be null, in which case we abort
-        self.ptr = match NonNull::new(new_ptr as *mut T) {
-            Some(p) => p,
-            None => alloc::handle_alloc_error(new_layout),
+        self.ptr = new_ptr
+            .map_err(|_| TryReserveError::AllocError { layout: new_layout })?
+            .cast();
+        self.cap = new_cap;
+        Ok(())
+    }
+
+    /// Reallocates the buffer down to `new_cap` slots. Callers are responsible
+    /// for making sure no initialized elements live past `new_cap`.
+    pub(super) fn shrink_to(&mut self, new_cap: usize) {
+        if std::mem::size_of::<T>() == 0 || new_cap >= self.cap {
+            return;
+        }
+
+        let old_layout = Layout::array::<T>(self.cap).unwrap();
+
+        if new_cap == 0 {
+            unsafe { self.alloc.deallocate(self.ptr.cast(), old_layout) };
+            self.ptr = NonNull::dangling();
+            self.cap = 0;
+            return;
+        }
+
+        let new_layout = Layout::array::<T>(new_cap).unwrap();
+        self.ptr = match unsafe { self.alloc.shrink(self.ptr.cast(), old_layout, new_layout) } {
+            Ok(p) => p.cast(),
+            Err(_) => alloc::handle_alloc_error(new_layout),
         };
         self.cap = new_cap;
     }
 }
 
-impl <T> Drop for RawList<T> {
+impl <T, A: Allocator> Drop for RawList<T, A> {
     fn drop(&mut self) {
         let elem_size = std::mem::size_of::<T>();
 
         if self.cap != 0 && elem_size != 0 {
             let layout = Layout::array::<T>(self.cap).unwrap();
             unsafe {
-                alloc::dealloc(self.ptr.as_ptr() as *mut u8, layout);
+                self.alloc.deallocate(self.ptr.cast(), layout);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_capacity_allocates_up_front() {
+        let raw: RawList<u64> = RawList::with_capacity(8);
+        assert_eq!(raw.cap, 8, "Capacity should be set to the requested size");
+        assert_ne!(raw.ptr, NonNull::dangling(), "A real allocation should not be dangling");
+        assert_eq!(raw.ptr.as_ptr() as usize % std::mem::align_of::<u64>(), 0, "The buffer should be aligned for T");
+    }
+
+    #[test]
+    fn test_with_capacity_zero_or_zst_matches_new() {
+        let empty: RawList<u64> = RawList::with_capacity(0);
+        assert_eq!(empty.cap, 0, "Zero capacity should not allocate");
+        assert_eq!(empty.ptr, NonNull::dangling());
+
+        let zst: RawList<()> = RawList::with_capacity(8);
+        assert_eq!(zst.cap, usize::MAX, "Zero sized types should report unlimited capacity");
+    }
+
+    #[test]
+    fn test_grow_to_jumps_in_one_step() {
+        let mut raw: RawList<u32> = RawList::new();
+        raw.grow_to(100);
+        assert_eq!(raw.cap, 100, "grow_to should go straight to the requested capacity");
+        raw.grow_to(101);
+        assert_eq!(raw.cap, 200, "grow_to should at least double to keep growth amortized");
+        raw.grow_to(50);
+        assert_eq!(raw.cap, 200, "grow_to should never shrink");
+    }
+
+    #[test]
+    fn test_grow_doubles() {
+        let mut raw: RawList<u32> = RawList::new();
+        let mut caps = Vec::new();
+        for _ in 0..5 {
+            raw.grow();
+            caps.push(raw.cap);
+        }
+        assert_eq!(caps, vec![1, 2, 4, 8, 16]);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_grow_zst_panics() {
+        let mut raw: RawList<()> = RawList::new();
+        raw.grow();
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity overflow")]
+    fn test_with_capacity_overflow_panics() {
+        let _raw: RawList<u64> = RawList::with_capacity(usize::MAX / 4);
+    }
+}
@@ -0,0 +1,62 @@
+use std::iter::FusedIterator;
+
+use crate::list::{Allocator, Drain, Global, List};
+
+/// Iterator returned by `List::splice`. Yields the removed elements and
+/// inserts the replacements when dropped
+pub struct Splice<'a, T: 'a, I: Iterator<Item = T>, A: Allocator = Global> {
+    pub(super) drain: Drain<'a, T, A>,
+    pub(super) replace_with: I,
+}
+
+impl <'a, T, I: Iterator<Item = T>, A: Allocator> Drop for Splice<'a, T, I, A> {
+    fn drop(&mut self) {
+        self.drain.by_ref().for_each(drop);
+
+        // Collecting first means the tail only has to move once, however
+        // the replacement's length compares to the removed range
+        let mut replacement: List<T> = self.replace_with.by_ref().collect();
+        let count = replacement.len;
+        if count == 0 {
+            return;
+        }
+
+        unsafe {
+            let list = self.drain.list.as_mut();
+            let start = list.len;
+            list.reserve(count + self.drain.tail_len);
+            std::ptr::copy(
+                list.ptr().add(self.drain.tail_start),
+                list.ptr().add(start + count),
+                self.drain.tail_len
+            );
+            std::ptr::copy_nonoverlapping(replacement.ptr(), list.ptr().add(start), count);
+            replacement.len = 0;
+            list.len = start + count;
+            // The tail is already in place, the `Drain` only has to bump len
+            self.drain.tail_start = list.len;
+        }
+    }
+}
+
+impl <'a, T, I: Iterator<Item = T>, A: Allocator> Iterator for Splice<'a, T, I, A> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.drain.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.drain.size_hint()
+    }
+}
+
+impl <'a, T, I: Iterator<Item = T>, A: Allocator> DoubleEndedIterator for Splice<'a, T, I, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.drain.next_back()
+    }
+}
+
+impl <'a, T, I: Iterator<Item = T>, A: Allocator> ExactSizeIterator for Splice<'a, T, I, A> {}
+
+impl <'a, T, I: Iterator<Item = T>, A: Allocator> FusedIterator for Splice<'a, T, I, A> {}
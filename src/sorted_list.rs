@@ -0,0 +1,119 @@
+use std::{fmt, ops::Deref};
+
+use crate::list::List;
+
+/// A `List` that keeps its elements in ascending order. Only shared access
+/// to the elements is handed out so the order can't be broken from outside
+pub struct SortedList<T> {
+    list: List<T>,
+}
+
+impl <T: Ord> SortedList<T> {
+    pub fn new() -> SortedList<T> {
+        SortedList { list: List::new() }
+    }
+
+    /// Inserts `val` at its sorted position, after any elements equal to it
+    pub fn insert(&mut self, val: T) {
+        let index = self.list.partition_point(|x| x <= &val);
+        self.list.insert(index, val);
+    }
+
+    pub fn contains(&self, val: &T) -> bool {
+        self.list.binary_search(val).is_ok()
+    }
+
+    /// Removes one element equal to `val` and returns it, or `None` if
+    /// there is no such element
+    pub fn remove_value(&mut self, val: &T) -> Option<T> {
+        let index = self.list.binary_search(val).ok()?;
+        Some(self.list.remove(index))
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        self.list.as_slice()
+    }
+
+    /// Consumes the sorted list and hands back the underlying `List`
+    pub fn into_list(self) -> List<T> {
+        self.list
+    }
+}
+
+impl <T> Deref for SortedList<T> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        &self.list
+    }
+}
+
+impl <T: Ord> Default for SortedList<T> {
+    fn default() -> Self {
+        SortedList::new()
+    }
+}
+
+impl <T: Ord> FromIterator<T> for SortedList<T> {
+    /// Collects everything first and sorts once instead of inserting one by one
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = List::from_iter(iter);
+        list.sort();
+        SortedList { list }
+    }
+}
+
+impl <T: fmt::Debug> fmt::Debug for SortedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.list, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_keeps_order() {
+        let mut sorted = SortedList::new();
+        for val in [3, 1, 2, 1] {
+            sorted.insert(val);
+        }
+        assert_eq!(sorted.as_slice(), &[1, 1, 2, 3], "Elements should stay sorted as they are inserted");
+        assert!(sorted.contains(&2), "contains should find an inserted element");
+        assert!(!sorted.contains(&9), "contains should not find a missing element");
+    }
+
+    #[test]
+    fn test_insert_equal_goes_last() {
+        // Ordered by the first field only, the second tells equal elements apart
+        #[derive(Debug, PartialEq, Eq)]
+        struct Keyed(i32, &'static str);
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Keyed {
+            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+
+        let mut sorted = SortedList::new();
+        sorted.insert(Keyed(1, "first"));
+        sorted.insert(Keyed(0, "zero"));
+        sorted.insert(Keyed(1, "second"));
+        assert_eq!(sorted[1].1, "first");
+        assert_eq!(sorted[2].1, "second", "Equal elements should keep insertion order");
+    }
+
+    #[test]
+    fn test_remove_value() {
+        let mut sorted: SortedList<i32> = [5, 3, 3, 8].into_iter().collect();
+        assert_eq!(sorted.as_slice(), &[3, 3, 5, 8], "Collecting should sort");
+        assert_eq!(sorted.remove_value(&3), Some(3));
+        assert_eq!(sorted.as_slice(), &[3, 5, 8], "Only one matching element should be removed");
+        assert_eq!(sorted.remove_value(&4), None, "Removing a missing value should return None");
+        assert_eq!(sorted.into_list(), [3, 5, 8]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt;
+
+use crate::list::List;
+
+/// A last in, first out stack. A thin wrapper over `List` that only exposes
+/// the operations that make sense for a stack
+pub struct Stack<T> {
+    list: List<T>,
+}
+
+impl <T> Stack<T> {
+    pub fn new() -> Stack<T> {
+        Stack { list: List::new() }
+    }
+
+    /// Pushes `val` onto the top of the stack
+    pub fn push(&mut self, val: T) {
+        self.list.push(val);
+    }
+
+    /// Removes and returns the element on top of the stack
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop()
+    }
+
+    /// Returns the element on top of the stack without removing it
+    pub fn peek(&self) -> Option<&T> {
+        self.list.last()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Drops every element, keeping the allocated buffer
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+}
+
+impl <T> Default for Stack<T> {
+    fn default() -> Self {
+        Stack::new()
+    }
+}
+
+impl <T> FromIterator<T> for Stack<T> {
+    /// Pushes the items in order, so the last one ends up on top
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Stack { list: List::from_iter(iter) }
+    }
+}
+
+impl <T> Extend<T> for Stack<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.list.extend(iter);
+    }
+}
+
+/// Lists the elements from the bottom of the stack to the top
+impl <T: fmt::Debug> fmt::Debug for Stack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.list, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    // Helper function to create a new stack
+    fn ns<T>() -> Stack<T> {
+        Stack::new()
+    }
+
+    #[test]
+    fn test_new_stack_is_empty() {
+        let s: Stack<i32> = ns();
+        assert!(s.peek().is_none(), "New stack should be empty");
+        assert!(s.is_empty());
+        assert_eq!(s.len(), 0);
+    }
+
+    #[test]
+    fn test_push_single_element() {
+        let mut s = ns();
+        s.push(42);
+        assert_eq!(s.peek(), Some(&42), "Peek should return the pushed element");
+        assert_eq!(s.len(), 1);
+    }
+
+    #[test]
+    fn test_push_pop_single_element() {
+        let mut s = ns();
+        s.push(42);
+        assert_eq!(s.pop(), Some(42), "Pop should return the pushed element");
+        assert!(s.peek().is_none(), "Stack should be empty after pop");
+    }
+
+    #[test]
+    fn test_push_multiple_elements() {
+        let mut s = ns();
+        s.push(1);
+        s.push(2);
+        s.push(3);
+        assert_eq!(s.peek(), Some(&3), "Peek should return the last pushed element");
+    }
+
+    #[test]
+    fn test_pop_multiple_elements() {
+        let mut s = ns();
+        s.push(1);
+        s.push(2);
+        s.push(3);
+        assert_eq!(s.pop(), Some(3), "First pop should return 3");
+        assert_eq!(s.pop(), Some(2), "Second pop should return 2");
+        assert_eq!(s.pop(), Some(1), "Third pop should return 1");
+        assert_eq!(s.pop(), None, "Pop on empty stack should return None");
+    }
+
+    #[test]
+    fn test_interleaved_push_pop() {
+        let mut s = ns();
+        s.push(1);
+        s.push(2);
+        assert_eq!(s.pop(), Some(2));
+        s.push(3);
+        assert_eq!(s.pop(), Some(3), "Pop should return the most recent push");
+        assert_eq!(s.pop(), Some(1));
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn test_clear_drops_elements() {
+        let rc = Rc::new(());
+        let mut s: Stack<Rc<()>> = (0..4).map(|_| rc.clone()).collect();
+        assert_eq!(Rc::strong_count(&rc), 5);
+        s.clear();
+        assert_eq!(Rc::strong_count(&rc), 1, "Clear should drop every element");
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    fn test_from_iter_and_extend() {
+        let mut s: Stack<i32> = (1..=3).collect();
+        assert_eq!(s.peek(), Some(&3), "The last collected item should be on top");
+        s.extend([4, 5]);
+        assert_eq!(s.len(), 5);
+        assert_eq!(s.pop(), Some(5));
+        assert_eq!(format!("{:?}", s), "[1, 2, 3, 4]", "Debug should list bottom to top");
+    }
+}